@@ -1,6 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
 
-#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq, EnumIter)]
 pub enum Action {
     CrackStudentBodyRightTackle,
     Curls,
@@ -29,52 +30,45 @@ impl Action {
 
     /// Returns `true` if `self` is a play action.
     pub fn is_play_action(&self) -> bool {
-        if let Self::PlayActionBoot | Self::PlayActionComebacks | Self::PlayActionPowerZero = self {
-            true
-        } else {
-            false
-        }
+        matches!(
+            self,
+            Self::PlayActionBoot | Self::PlayActionComebacks | Self::PlayActionPowerZero
+        )
     }
 
     /// Returns `true` if `self` is a halfback.
     pub fn is_halfback(&self) -> bool {
-        if let Self::HalfbackSlam | Self::HalfbackSlipScreen | Self::HalfbackSweep = self {
-            true
-        } else {
-            true
-        }
+        matches!(
+            self,
+            Self::HalfbackSlam | Self::HalfbackSlipScreen | Self::HalfbackSweep
+        )
     }
 
     /// Returns `true` if `self` is a running play.
     pub fn is_run(&self) -> bool {
-        if let Self::HalfbackSlam
-        | Self::SpeedOption
-        | Self::HalfbackSweep
-        | Self::PowerZero
-        | Self::CrackStudentBodyRightTackle = self
-        {
-            true
-        } else {
-            false
-        }
+        matches!(
+            self,
+            Self::HalfbackSlam
+                | Self::SpeedOption
+                | Self::HalfbackSweep
+                | Self::PowerZero
+                | Self::CrackStudentBodyRightTackle
+        )
     }
 
     /// Returns `true` if `self` is a passing play.
+    /// `Action::Unknown` is neither a run nor a pass.
     pub fn is_pass(&self) -> bool {
-        !self.is_run()
+        !self.is_run() && !self.is_unknown()
     }
 
     /// Returns `true` if `self` is `Event::Unknown`.
     pub fn is_unknown(&self) -> bool {
-        if let Self::Unknown = self {
-            true
-        } else {
-            false
-        }
+        matches!(self, Self::Unknown)
     }
 
     /// Returns the `Playset` that this action belongs to.
-    /// Returns `None` if `Event::Unknown`
+    /// Returns `None` if `Event::Unknown`, or not part of a playset, like `HailMary`.
     pub fn playset(&self) -> Option<Playset> {
         if self.is_unknown() {
             return None;
@@ -92,12 +86,12 @@ impl Action {
             Self::Mesh | Self::CrackStudentBodyRightTackle | Self::FleaFlicker => {
                 Playset::IFormTight
             }
-            _ => unreachable!(),
+            _ => return None,
         })
     }
 
     /// Returns the `Key` that this action belongs to.
-    /// Returns `None` if `Event::Unknown`
+    /// Returns `None` if `Event::Unknown`, or not on a key, like `HailMary`.
     pub fn key(&self) -> Option<Key> {
         if self.is_unknown() {
             return None;
@@ -117,12 +111,12 @@ impl Action {
             | Self::PlayActionComebacks
             | Self::PlayActionPowerZero
             | Self::FleaFlicker => Key::Triangle,
-            _ => unreachable!(),
+            _ => return None,
         })
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash, EnumIter)]
 pub enum Playset {
     PistolSpread,
     ShotgunTripleWingsOffset,
@@ -131,7 +125,7 @@ pub enum Playset {
     IFormTight,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, Hash, EnumIter)]
 pub enum Key {
     Square,
     X,
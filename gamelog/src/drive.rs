@@ -0,0 +1,429 @@
+use crate::{
+    Event, FieldGoalResult, Play, Quarter, ScorePoints, Team, TerrainState, TurnoverKind, YardLine,
+};
+
+/// How a drive came to an end.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DriveEnd {
+    /// The first non-PAT score of the drive.
+    Score(ScorePoints),
+    /// The offence punted the ball away.
+    Punt,
+    /// Possession changed without a score, and why, if it was noted.
+    Turnover(Option<TurnoverKind>),
+    /// The half, or an overtime period, ended.
+    QuarterEnd,
+    /// The log ended.
+    GameEnd,
+}
+
+/// A single possession by one team.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drive {
+    pub offence: Team,
+    /// Index into `Game.events` of the event the drive starts at.
+    pub start: usize,
+    /// The quarter the drive started in.
+    pub quarter: Quarter,
+    /// Every event of the drive, including the `Kickoff` or `Turnover` opening it.
+    pub events: Vec<Event>,
+    pub end: DriveEnd,
+}
+
+impl Drive {
+    pub fn plays(&self) -> Vec<&Play> {
+        self.events
+            .iter()
+            .filter_map(|event| {
+                if let Event::Play(play) = event {
+                    Some(play)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// The distance and result of every field goal the offence attempted.
+    pub fn field_goal_attempts(&self) -> Vec<(u8, &FieldGoalResult)> {
+        self.events
+            .iter()
+            .filter_map(|event| {
+                if let Event::FieldGoalAttempt { distance, result } = event {
+                    Some((*distance, result))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    pub fn penalties(&self) -> Vec<&TerrainState> {
+        self.events
+            .iter()
+            .filter_map(|event| {
+                if let Event::Penalty(terrain, _) = event {
+                    Some(terrain)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Yards from the offence's own goal line at each event, indexed like `events`.
+    ///
+    /// Positions noted in the log are carried to the events around them by the
+    /// change in yards-to-go between plays. Penalties are measured along with the
+    /// play following them, as the yards-to-go after that play already include them.
+    pub fn field_positions(&self) -> Vec<Option<u8>> {
+        // Indices of the events that yards-to-go can be measured between.
+        let measurable: Vec<usize> = self
+            .events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| {
+                matches!(
+                    event,
+                    Event::Kickoff(..) | Event::Punt { .. } | Event::Turnover(..) | Event::Play(_)
+                )
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let mut positions: Vec<Option<u8>> = self
+            .events
+            .iter()
+            .map(|event| match event {
+                Event::Kickoff(_, position, _) | Event::Punt { position, .. } => {
+                    position.map(|yard_line| yard_line.from_own_goal())
+                }
+                Event::Play(play) => play.position.map(|yard_line| yard_line.from_own_goal()),
+                _ => None,
+            })
+            .collect();
+
+        let deltas: Vec<Option<i8>> = measurable
+            .windows(2)
+            .map(|pair| self.events[pair[0]].delta(&self.events[pair[1]]))
+            .collect();
+
+        let shift = |position: u8, delta: i16| (position as i16 + delta).clamp(0, 100) as u8;
+
+        // Carry forward, then back to anything before the first noted position.
+        for (k, delta) in deltas.iter().enumerate() {
+            let (from, to) = (measurable[k], measurable[k + 1]);
+
+            if positions[to].is_none()
+                && let (Some(position), Some(delta)) = (positions[from], delta)
+            {
+                positions[to] = Some(shift(position, *delta as i16))
+            }
+        }
+
+        for (k, delta) in deltas.iter().enumerate().rev() {
+            let (from, to) = (measurable[k], measurable[k + 1]);
+
+            if positions[from].is_none()
+                && let (Some(position), Some(delta)) = (positions[to], delta)
+            {
+                positions[from] = Some(shift(position, -(*delta as i16)))
+            }
+        }
+
+        // Everything else is wherever the ball last was.
+        let mut last = None;
+
+        self.events
+            .iter()
+            .zip(positions)
+            .map(|(event, position)| {
+                if matches!(
+                    event,
+                    Event::Kickoff(..) | Event::Punt { .. } | Event::Turnover(..) | Event::Play(_)
+                ) {
+                    last = position
+                }
+
+                last
+            })
+            .collect()
+    }
+
+    /// Yards from the offence's own goal line that the drive started at, if known.
+    pub fn start_position(&self) -> Option<u8> {
+        *self.field_positions().first()?
+    }
+
+    /// Returns `true` if the offence got inside the opponent's 20.
+    pub fn reached_red_zone(&self) -> bool {
+        self.field_positions()
+            .iter()
+            .flatten()
+            .any(|position| YardLine::Own(*position).is_red_zone())
+    }
+
+    /// Index into `Game.events` of the first event after the drive.
+    pub fn end_index(&self) -> usize {
+        self.start + self.events.len()
+    }
+}
+
+/// A drive still being collected, whose offence may not be known yet.
+pub(crate) struct PartialDrive {
+    pub offence: Option<Team>,
+    pub start: usize,
+    pub quarter: Quarter,
+    pub events: Vec<Event>,
+}
+
+impl PartialDrive {
+    /// Completes the drive, scores taking precedence over `end`.
+    ///
+    /// Drives with no known offence are assumed to belong to whoever did not
+    /// receive the ball next, which needs both `teams` and `next` to be known.
+    pub fn finish(self, end: DriveEnd, next: Option<&Team>, teams: &[Team]) -> Option<Drive> {
+        let offence = match self.offence {
+            Some(team) => team,
+            None if teams.len() == 2 && next.is_some_and(|next| teams.contains(next)) => {
+                teams.iter().find(|team| Some(*team) != next)?.to_owned()
+            }
+            None => return None,
+        };
+
+        let score = self.events.iter().find_map(|event| match event {
+            Event::Score(points, _) if !points.is_pat() => Some(points.to_owned()),
+            _ => None,
+        });
+
+        Some(Drive {
+            offence,
+            start: self.start,
+            quarter: self.quarter,
+            events: self.events,
+            end: match score {
+                Some(points) => DriveEnd::Score(points),
+                None => end,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::play;
+    use crate::*;
+
+    fn game(events: Vec<Event>) -> Game {
+        Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events,
+        }
+    }
+
+    #[test]
+    fn drives() {
+        let a = game(vec![
+            Event::Quarter(Quarter::First),
+            Event::Kickoff(Team::Nebraska, None, None),
+            Event::Play(Play::default()),
+            Event::Quarter(Quarter::Second),
+            Event::Play(Play::default()),
+            Event::Score(ScorePoints::Touchdown, None),
+            Event::Score(ScorePoints::PatFieldGoal, None),
+            Event::Kickoff(Team::Iowa, None, None),
+            Event::Penalty(TerrainState::Yards(15), None),
+            Event::Play(Play::default()),
+            Event::Turnover(Team::Nebraska, Some(TurnoverKind::Fumble)),
+            Event::Play(Play::default()),
+            Event::Quarter(Quarter::Third),
+            Event::Kickoff(Team::Iowa, None, None),
+            Event::Play(Play::default()),
+        ]);
+
+        let drives = a.drives();
+
+        assert!(drives.len() == 4);
+
+        assert!(drives[0].offence == Team::Nebraska);
+        assert!(drives[0].start == 1);
+        assert!(drives[0].quarter == Quarter::First);
+        assert!(drives[0].plays().len() == 2);
+        assert!(drives[0].end == DriveEnd::Score(ScorePoints::Touchdown));
+
+        assert!(drives[1].offence == Team::Iowa);
+        assert!(drives[1].quarter == Quarter::Second);
+        assert!(drives[1].penalties() == vec![&TerrainState::Yards(15)]);
+        assert!(drives[1].end == DriveEnd::Turnover(Some(TurnoverKind::Fumble)));
+
+        assert!(drives[2].offence == Team::Nebraska);
+        assert!(drives[2].end == DriveEnd::QuarterEnd);
+        assert!(drives[2].end_index() == 12);
+
+        assert!(drives[3].offence == Team::Iowa);
+        assert!(drives[3].quarter == Quarter::Third);
+        assert!(drives[3].end == DriveEnd::GameEnd);
+    }
+
+    #[test]
+    fn infer_offence() {
+        let a = game(vec![
+            Event::Quarter(Quarter::First),
+            Event::Play(Play::default()),
+            Event::Turnover(Team::Iowa, None),
+            Event::Play(Play::default()),
+            Event::Turnover(Team::Nebraska, None),
+        ]);
+
+        // Nothing to infer from.
+        let b = game(vec![
+            Event::Quarter(Quarter::First),
+            Event::Play(Play::default()),
+            Event::Turnover(Team::Iowa, None),
+        ]);
+
+        assert!(
+            a.drives()
+                .iter()
+                .map(|drive| drive.offence.to_owned())
+                .collect::<Vec<Team>>()
+                == vec![Team::Nebraska, Team::Iowa, Team::Nebraska]
+        );
+        assert!(b.drives().len() == 1);
+    }
+
+    #[test]
+    fn turnovers() {
+        let a = game(vec![
+            Event::Kickoff(Team::Nebraska, None, None),
+            Event::Play(Play::default()),
+            Event::Turnover(Team::Iowa, Some(TurnoverKind::Interception)),
+            Event::Play(Play::default()),
+            Event::Turnover(Team::Nebraska, Some(TurnoverKind::Downs)),
+            Event::Play(Play::default()),
+            Event::Turnover(Team::Iowa, Some(TurnoverKind::Fumble)),
+            Event::Turnover(Team::Nebraska, None),
+            Event::Turnover(Team::Iowa, Some(TurnoverKind::Interception)),
+        ]);
+
+        let drives = a.drives();
+
+        assert!(drives[0].end == DriveEnd::Turnover(Some(TurnoverKind::Interception)));
+        assert!(drives[3].end == DriveEnd::Turnover(None));
+
+        assert!(a.turnover_margin(Team::Iowa) == 3);
+        assert!(a.turnover_margin(Team::Nebraska) == -3);
+        assert!(a.turnover_margin(Team::Colorado) == 0);
+        assert!(
+            a.turnovers_by_kind(Team::Nebraska)
+                == vec![
+                    (TurnoverKind::Interception, 2),
+                    (TurnoverKind::Fumble, 1),
+                    (TurnoverKind::Downs, 0),
+                    (TurnoverKind::MissedFieldGoal, 0),
+                ]
+        );
+        assert!(a.turnovers_by_kind(Team::Iowa)[2] == (TurnoverKind::Downs, 1));
+    }
+
+    #[test]
+    fn team_events() {
+        let a = game(vec![
+            Event::Kickoff(Team::Nebraska, None, None),
+            Event::Play(Play::default()),
+            Event::Turnover(Team::ArizonaState, None),
+            Event::Play(Play::default()),
+            Event::Play(Play::default()),
+            Event::Kickoff(Team::Nebraska, None, None),
+            Event::Score(ScorePoints::Touchdown, None),
+        ]);
+
+        assert!(
+            a.team_events(Team::Nebraska)
+                == vec![
+                    Event::Kickoff(Team::Nebraska, None, None),
+                    Event::Play(Play::default()),
+                    Event::Kickoff(Team::Nebraska, None, None),
+                    Event::Score(ScorePoints::Touchdown, None),
+                ]
+        );
+        assert!(a.team_plays(Team::Nebraska) == 1);
+        assert!(a.team_plays(Team::ArizonaState) == 2);
+    }
+
+    #[test]
+    fn deltas() {
+        let a = game(vec![
+            Event::Quarter(Quarter::First),
+            Event::Kickoff(Team::Nebraska, None, None),
+            play(Action::Unknown, Down::First, TerrainState::Yards(10)),
+            play(Action::Unknown, Down::Second, TerrainState::Yards(13)),
+            play(Action::Unknown, Down::Third, TerrainState::Yards(8)),
+            Event::Turnover(Team::ArizonaState, None),
+            play(Action::Unknown, Down::First, TerrainState::Yards(10)),
+            play(Action::Unknown, Down::Second, TerrainState::Yards(10)),
+            Event::Turnover(Team::Nebraska, None),
+            play(Action::Unknown, Down::Second, TerrainState::Yards(12)),
+            Event::Quarter(Quarter::Second),
+            play(Action::Unknown, Down::First, TerrainState::Yards(10)),
+            Event::Kickoff(Team::ArizonaState, None, None),
+        ]);
+
+        assert!(a.deltas(Team::Nebraska) == vec![10, -3, 5, -2, 12, 10]);
+        assert!(a.deltas(Team::ArizonaState) == vec![10, 0]);
+        assert!(game(vec![]).deltas(Team::Nebraska).is_empty());
+    }
+
+    #[test]
+    fn field_positions() {
+        let play = |down: Down, yards: u8, position: Option<YardLine>| {
+            Event::Play(Play {
+                action: Action::Unknown,
+                down: Some(down),
+                terrain: Some(TerrainState::Yards(yards)),
+                position,
+            })
+        };
+
+        let a = game(vec![
+            Event::Kickoff(Team::Nebraska, Some(YardLine::Own(25)), None),
+            play(Down::Second, 4, None),
+            Event::Quarter(Quarter::Second),
+            play(Down::First, 10, None),
+            Event::Penalty(TerrainState::Yards(5), None),
+            play(Down::Second, 12, Some(YardLine::Opponent(45))),
+            play(Down::First, 10, None),
+            play(Down::First, 10, None),
+            Event::Turnover(Team::Iowa, None),
+            play(Down::Second, 7, None),
+            play(Down::Third, 2, Some(YardLine::Opponent(12))),
+        ]);
+
+        let drives = a.drives();
+
+        assert!(
+            drives[0].field_positions()
+                == vec![
+                    Some(25),
+                    Some(31),
+                    Some(31),
+                    Some(35),
+                    Some(35),
+                    Some(55),
+                    Some(67),
+                    Some(77),
+                ]
+        );
+        assert!(drives[0].start_position() == Some(25));
+        assert!(!drives[0].reached_red_zone());
+
+        // Worked back from the noted position.
+        assert!(drives[1].field_positions() == vec![Some(80), Some(83), Some(88)]);
+        assert!(drives[1].start_position() == Some(80));
+        assert!(drives[1].reached_red_zone());
+
+        assert!(a.avg_start_position(Team::Nebraska) == Some(25.0));
+        assert!(a.red_zone_trips(Team::Iowa) == 1);
+    }
+}
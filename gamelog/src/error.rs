@@ -3,14 +3,51 @@ use std::{fmt, io};
 #[derive(Debug)]
 pub enum LogFileError {
     FailedToOpen(io::Error),
+    FailedToWrite(io::Error),
     RonSpannedError(ron::error::SpannedError),
+    RonError(ron::Error),
+    IncompatibleVersion {
+        game_index: usize,
+        found: semver::Version,
+        supported: semver::VersionReq,
+    },
 }
 
 impl fmt::Display for LogFileError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::FailedToOpen(err) => write!(f, "{}", err),
+            Self::FailedToWrite(err) => write!(f, "{}", err),
             Self::RonSpannedError(err) => write!(f, "{}", err),
+            Self::RonError(err) => write!(f, "{}", err),
+            Self::IncompatibleVersion {
+                game_index,
+                found,
+                supported,
+            } => write!(
+                f,
+                "Game {} has version {}, expected {}",
+                game_index, found, supported
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum MigrateError {
+    FailedToOpen(io::Error),
+    RonSpannedError(ron::error::SpannedError),
+    UnsupportedVersion(semver::Version),
+    MixedVersions(Vec<semver::Version>),
+}
+
+impl fmt::Display for MigrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FailedToOpen(err) => write!(f, "{}", err),
+            Self::RonSpannedError(err) => write!(f, "{}", err),
+            Self::UnsupportedVersion(ver) => write!(f, "No known schema for version {}", ver),
+            Self::MixedVersions(vers) => write!(f, "Games span several schemas: {:?}", vers),
         }
     }
 }
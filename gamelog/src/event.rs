@@ -1,16 +1,97 @@
-use crate::{Down, Play, Quarter, TerrainState, error};
-use serde::Deserialize;
+use crate::{Down, FieldGoalResult, Penalty, Play, Quarter, TerrainState, YardLine, error};
+use serde::{Deserialize, Serialize, Serializer};
+use strum::{EnumIter, EnumString};
 
 type Offence = Team;
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub enum Event {
-    Kickoff(Offence),
+    /// Optionally records where the offence takes over, and how far it returned the kick.
+    #[serde(serialize_with = "serialize_kickoff")]
+    Kickoff(
+        Offence,
+        #[serde(default)] Option<YardLine>,
+        #[serde(default)] Option<u8>,
+    ),
     Play(Play),
-    Turnover(Offence),
-    Penalty(TerrainState),
-    Score(ScorePoints),
+    /// Optionally records why possession changed.
+    #[serde(serialize_with = "serialize_turnover")]
+    Turnover(Offence, #[serde(default)] Option<TurnoverKind>),
+    /// Penalties without details count against the team on offence.
+    #[serde(serialize_with = "serialize_penalty")]
+    Penalty(TerrainState, #[serde(default)] Option<Penalty>),
+    /// Scores count for the team on offence, or the defence for a safety,
+    /// unless a team is given explicitly.
+    #[serde(serialize_with = "serialize_score")]
+    Score(ScorePoints, #[serde(default)] Option<Team>),
     Quarter(Quarter),
+    /// `offence` receives the punt, as with `Kickoff`.
+    /// `distance` is measured from the line of scrimmage.
+    Punt {
+        offence: Offence,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        distance: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        return_yards: Option<u8>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        position: Option<YardLine>,
+    },
+    /// Good attempts are still followed by `Score(FieldGoal)`,
+    /// and misses by the `Turnover`, as they happened.
+    FieldGoalAttempt {
+        distance: u8,
+        result: FieldGoalResult,
+    },
+}
+
+/// Leaves out the trailing parts of a `Kickoff` that were not given, as it would be written by hand.
+fn serialize_kickoff<S: Serializer>(
+    offence: &Offence,
+    position: &Option<YardLine>,
+    return_yards: &Option<u8>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match (position, return_yards) {
+        (None, None) => offence.serialize(serializer),
+        (Some(position), None) => (offence, position).serialize(serializer),
+        (position, Some(return_yards)) => (offence, position, return_yards).serialize(serializer),
+    }
+}
+
+/// Leaves out the kind of a `Turnover` if it was not given, as it would be written by hand.
+fn serialize_turnover<S: Serializer>(
+    offence: &Offence,
+    kind: &Option<TurnoverKind>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match kind {
+        None => offence.serialize(serializer),
+        Some(kind) => (offence, kind).serialize(serializer),
+    }
+}
+
+/// Leaves out the team of a `Score` if it was not given, as it would be written by hand.
+fn serialize_score<S: Serializer>(
+    points: &ScorePoints,
+    team: &Option<Team>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match team {
+        None => points.serialize(serializer),
+        Some(team) => (points, team).serialize(serializer),
+    }
+}
+
+/// Leaves out the details of a `Penalty` if none were given, as it would be written by hand.
+fn serialize_penalty<S: Serializer>(
+    distance: &TerrainState,
+    details: &Option<Penalty>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match details {
+        None => distance.serialize(serializer),
+        Some(details) => (distance, details).serialize(serializer),
+    }
 }
 
 impl Event {
@@ -19,7 +100,9 @@ impl Event {
 
         fn make_play(event: &Event) -> Option<Play> {
             match event {
-                Event::Kickoff(_) | Event::Turnover(_) => Some(Play::default()),
+                Event::Kickoff(..) | Event::Punt { .. } | Event::Turnover(..) => {
+                    Some(Play::default())
+                }
                 Event::Play(play) => {
                     let p = play.to_owned();
 
@@ -37,7 +120,7 @@ impl Event {
         }
 
         let preceeding = make_play(self)?;
-        let following = if let Event::Turnover(_) = following {
+        let following = if let Event::Turnover(..) | Event::Punt { .. } = following {
             // I should really just early return
             // but this is too funny to look at.
             None?
@@ -70,8 +153,9 @@ impl Event {
 
     pub fn team(&self) -> Result<Team, error::NoTeamAttribute> {
         match self {
-            Self::Kickoff(team) => Ok(team.to_owned()),
-            Self::Turnover(team) => Ok(team.to_owned()),
+            Self::Kickoff(team, ..) => Ok(team.to_owned()),
+            Self::Punt { offence, .. } => Ok(offence.to_owned()),
+            Self::Turnover(team, _) => Ok(team.to_owned()),
             _ => Err(error::NoTeamAttribute),
         }
     }
@@ -85,7 +169,8 @@ impl Event {
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, EnumString)]
+#[strum(ascii_case_insensitive)]
 pub enum Team {
     ArizonaState,
     #[deprecated(since = "0.2.0", note = "Team left the project.")]
@@ -98,7 +183,7 @@ pub enum Team {
     TexasAnM,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
 pub enum ScorePoints {
     #[default]
     Touchdown,
@@ -111,6 +196,14 @@ pub enum ScorePoints {
 }
 
 impl ScorePoints {
+    /// Returns `true` if `self` is a point after touchdown attempt.
+    pub fn is_pat(&self) -> bool {
+        matches!(
+            self,
+            Self::PatFail | Self::PatTouchdown | Self::PatFieldGoal | Self::PatSafety
+        )
+    }
+
     pub fn to_points(&self) -> u8 {
         match &self {
             Self::Touchdown => 6,
@@ -124,60 +217,84 @@ impl ScorePoints {
     }
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, EnumIter)]
+pub enum TurnoverKind {
+    Interception,
+    Fumble,
+    Downs,
+    MissedFieldGoal,
+}
+
+impl TurnoverKind {
+    /// Returns `true` for interceptions and fumbles, the turnovers counted by
+    /// `Game::turnover_margin`.
+    pub fn is_takeaway(&self) -> bool {
+        matches!(self, Self::Interception | Self::Fumble)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
 
     #[test]
+    #[allow(clippy::partialeq_to_none)]
     fn delta() {
-        let kickoff = Event::Kickoff(Team::Nebraska);
+        let kickoff = Event::Kickoff(Team::Nebraska, None, None);
 
         let first_down = Event::Play(Play {
             action: Action::Unknown,
             down: Some(Down::First),
             terrain: Some(TerrainState::Yards(10)),
+            position: None,
         });
 
         let second_down = Event::Play(Play {
             action: Action::Unknown,
             down: Some(Down::Second),
             terrain: Some(TerrainState::Yards(10)),
+            position: None,
         });
 
         let third_down = Event::Play(Play {
             action: Action::Unknown,
             down: Some(Down::Third),
             terrain: Some(TerrainState::Yards(13)),
+            position: None,
         });
 
         let fourth_down = Event::Play(Play {
             action: Action::Unknown,
             down: Some(Down::Fourth),
             terrain: Some(TerrainState::Yards(5)),
+            position: None,
         });
 
-        let penalty = Event::Penalty(TerrainState::Yards(15));
+        let penalty = Event::Penalty(TerrainState::Yards(15), None);
 
-        let turnover = Event::Turnover(Team::Nebraska);
+        let turnover = Event::Turnover(Team::Nebraska, None);
 
         let noned_down = Event::Play(Play {
             action: Action::Unknown,
             down: None,
             terrain: None,
+            position: None,
         });
 
-        let score = Event::Score(ScorePoints::default());
+        let score = Event::Score(ScorePoints::default(), None);
 
         let goal_line = Event::Play(Play {
             action: Action::Unknown,
             down: Some(Down::First),
             terrain: Some(TerrainState::GoalLine),
+            position: None,
         });
 
         let inches = Event::Play(Play {
             action: Action::Unknown,
             down: Some(Down::First),
             terrain: Some(TerrainState::Inches),
+            position: None,
         });
 
         let quarter = Event::Quarter(Quarter::First);
@@ -1,8 +1,42 @@
 use crate::error;
-use serde::Deserialize;
-use std::{fs::File, path::PathBuf};
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs::File, io::Write, path::PathBuf};
 
-#[derive(Debug, Deserialize, Clone)]
+/// The options every logfile is parsed with.
+pub(crate) fn ron_options() -> ron::Options {
+    ron::Options::default()
+        .with_default_extension(ron::extensions::Extensions::EXPLICIT_STRUCT_NAMES)
+}
+
+/// How `LogFile::load` treats games outside of `crate::supported_versions`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum VersionPolicy {
+    /// Refuse any game outside of the supported range.
+    #[default]
+    Strict,
+    /// Attempt games from newer minor versions, warning about each one.
+    BestEffort,
+}
+
+/// A game that was loaded despite being newer than the supported range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionWarning {
+    pub game_index: usize,
+    pub found: semver::Version,
+    pub supported: semver::VersionReq,
+}
+
+impl fmt::Display for VersionWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Game {} has newer version {} than {}, loading on a best-effort basis",
+            self.game_index, self.found, self.supported
+        )
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct LogFile(pub Vec<super::Game>);
 
 impl LogFile {
@@ -18,37 +52,258 @@ impl LogFile {
         lowest
     }
 
-    /// Returns if the LogFile min version is compatible.
+    /// Returns if every game in the LogFile is of a supported version.
     pub fn is_compatible(&self) -> bool {
-        self.min_ver().cmp_precedence(&crate::MIN_VER).is_lt()
+        let supported = crate::supported_versions();
+
+        self.0.iter().all(|game| supported.matches(&game.version))
+    }
+
+    /// Checks `versions` against the supported range, according to `policy`.
+    /// Returns warnings for every newer game that `policy` lets through.
+    pub fn check_versions(
+        versions: &[semver::Version],
+        policy: VersionPolicy,
+    ) -> Result<Vec<VersionWarning>, error::LogFileError> {
+        let supported = crate::supported_versions();
+        let mut warnings = vec![];
+
+        for (game_index, found) in versions.iter().enumerate() {
+            if supported.matches(found) {
+                continue;
+            }
+
+            let newer_minor = found.major == crate::MIN_VER.major
+                && found.cmp_precedence(&crate::MIN_VER).is_gt();
+
+            if policy == VersionPolicy::BestEffort && newer_minor {
+                warnings.push(VersionWarning {
+                    game_index,
+                    found: found.to_owned(),
+                    supported: supported.to_owned(),
+                })
+            } else {
+                return Err(error::LogFileError::IncompatibleVersion {
+                    game_index,
+                    found: found.to_owned(),
+                    supported,
+                });
+            }
+        }
+
+        Ok(warnings)
+    }
+
+    /// Parses `src`, refusing it before deserializing the events
+    /// if any game's version is rejected by `policy`.
+    pub fn parse(
+        src: &str,
+        policy: VersionPolicy,
+    ) -> Result<(Self, Vec<VersionWarning>), error::LogFileError> {
+        let versions = match crate::migrate::game_versions(src) {
+            Ok(versions) => versions,
+            Err(err) => return Err(error::LogFileError::RonSpannedError(err)),
+        };
+
+        let warnings = Self::check_versions(&versions, policy)?;
+
+        match ron_options().from_str(src) {
+            Ok(log) => Ok((log, warnings)),
+            Err(err) => Err(error::LogFileError::RonSpannedError(err)),
+        }
+    }
+
+    /// Reads and parses the logfile at `path`. See `LogFile::parse`.
+    pub fn load(
+        path: PathBuf,
+        policy: VersionPolicy,
+    ) -> Result<(Self, Vec<VersionWarning>), error::LogFileError> {
+        match std::fs::read_to_string(path.as_path()) {
+            Ok(src) => Self::parse(&src, policy),
+            Err(err) => Err(error::LogFileError::FailedToOpen(err)),
+        }
+    }
+
+    /// Serializes `self` into the same extension-enabled RON that logfiles are
+    /// written by hand in.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        let config = ron::ser::PrettyConfig::default()
+            .struct_names(true)
+            .extensions(
+                ron::extensions::Extensions::IMPLICIT_SOME
+                    | ron::extensions::Extensions::UNWRAP_NEWTYPES
+                    | ron::extensions::Extensions::UNWRAP_VARIANT_NEWTYPES,
+            );
+
+        ron::Options::default().to_string_pretty(self, config)
+    }
+
+    /// Writes `self` out to `path`, creating or truncating the file.
+    pub fn write_to(&self, path: PathBuf) -> Result<(), error::LogFileError> {
+        let ron = match self.to_ron() {
+            Ok(s) => s,
+            Err(err) => return Err(error::LogFileError::RonError(err)),
+        };
+
+        let mut file = match std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path.as_path())
+        {
+            Ok(f) => f,
+            Err(err) => return Err(error::LogFileError::FailedToWrite(err)),
+        };
+
+        match writeln!(file, "{}", ron) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(error::LogFileError::FailedToWrite(err)),
+        }
     }
 }
 
+/// Parses without any version checks. Prefer `LogFile::load`.
 impl TryFrom<File> for LogFile {
     type Error = ron::error::SpannedError;
 
     fn try_from(file: File) -> Result<Self, Self::Error> {
-        ron::Options::default()
-            .with_default_extension(ron::extensions::Extensions::EXPLICIT_STRUCT_NAMES)
-            .from_reader(file)
+        ron_options().from_reader(file)
     }
 }
 
+/// Loads with `VersionPolicy::Strict`.
 impl TryFrom<PathBuf> for LogFile {
     type Error = error::LogFileError;
 
     fn try_from(path: PathBuf) -> Result<Self, Self::Error> {
-        match Self::try_from(
-            match std::fs::OpenOptions::new() // Defaults to setting all options false.
-                .read(true) // Only need ensure that reading is possible.
-                .open(path.as_path())
-            {
-                Ok(f) => f,
-                Err(err) => return Err(error::LogFileError::FailedToOpen(err)),
-            },
-        ) {
-            Ok(f) => Ok(f),
-            Err(err) => Err(error::LogFileError::RonSpannedError(err)),
+        match Self::load(path, VersionPolicy::Strict) {
+            Ok((log, _)) => Ok(log),
+            Err(err) => Err(err),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::gamelog;
+    use crate::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn round_trip() {
+        let original = gamelog();
+
+        // Unique to this run, and removed before anything can fail.
+        let path =
+            std::env::temp_dir().join(format!("gamelog_round_trip_{}.ron", std::process::id()));
+        let written = original
+            .write_to(path.to_owned())
+            .map(|_| LogFile::try_from(path.to_owned()));
+        let _ = std::fs::remove_file(path);
+        let written = written.unwrap().unwrap();
+
+        assert!(original == written);
+        assert!(original.to_ron().unwrap() == written.to_ron().unwrap());
+    }
+
+    #[test]
+    fn round_trip_optional_fields() {
+        let original = LogFile(vec![Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events: vec![
+                Event::Kickoff(Team::Nebraska, None, None),
+                Event::Kickoff(Team::Iowa, Some(YardLine::Own(25)), None),
+                Event::Play(Play {
+                    position: Some(YardLine::Opponent(30)),
+                    ..Default::default()
+                }),
+                Event::Score(ScorePoints::Touchdown, None),
+                Event::Score(ScorePoints::Safety, Some(Team::Nebraska)),
+                Event::Kickoff(Team::Nebraska, Some(YardLine::Own(30)), Some(20)),
+                Event::Punt {
+                    offence: Team::Iowa,
+                    distance: Some(40),
+                    return_yards: None,
+                    position: None,
+                },
+                Event::FieldGoalAttempt {
+                    distance: 33,
+                    result: FieldGoalResult::Blocked,
+                },
+                Event::Turnover(Team::Iowa, None),
+                Event::Turnover(Team::Nebraska, Some(TurnoverKind::Downs)),
+                Event::Penalty(TerrainState::Yards(5), None),
+                Event::Penalty(
+                    TerrainState::Yards(15),
+                    Some(Penalty {
+                        side: Some(Side::Defence),
+                        automatic_first_down: true,
+                        ..Default::default()
+                    }),
+                ),
+            ],
+        }]);
+
+        let ron = original.to_ron().unwrap();
+
+        // Left out when not given, as they would be written by hand.
+        assert!(ron.contains("Kickoff(Nebraska),"));
+        assert!(ron.contains("Score(Touchdown),"));
+        assert!(ron.contains("Penalty(Yards(5)),"));
+        assert!(ron.contains("Turnover(Iowa),"));
+        assert!(ron.contains("Punt(\n"));
+        assert!(!ron.contains("Accepted"));
+        assert!(!ron.contains("None"));
+
+        let (written, _) = LogFile::parse(&ron, VersionPolicy::Strict).unwrap();
+
+        assert!(original == written);
+    }
+
+    #[test]
+    fn is_compatible() {
+        let mut log = gamelog();
+        assert!(log.is_compatible());
+
+        log.0[0].version = semver::Version::new(0, 7, 9);
+        assert!(!log.is_compatible());
+
+        log.0[0].version = semver::Version::new(0, 9, 0);
+        assert!(!log.is_compatible());
+    }
+
+    #[test]
+    fn check_versions() {
+        let versions = vec![
+            semver::Version::new(0, 8, 0),
+            semver::Version::new(0, 8, 3),
+            semver::Version::new(0, 9, 1),
+        ];
+
+        assert!(matches!(
+            LogFile::check_versions(&versions, VersionPolicy::Strict),
+            Err(error::LogFileError::IncompatibleVersion { game_index: 2, .. })
+        ));
+
+        let warnings = LogFile::check_versions(&versions, VersionPolicy::BestEffort).unwrap();
+        assert!(warnings.len() == 1);
+        assert!(warnings[0].game_index == 2);
+
+        let older = vec![semver::Version::new(0, 7, 0)];
+        assert!(LogFile::check_versions(&older, VersionPolicy::BestEffort).is_err());
+
+        let newer_major = vec![semver::Version::new(1, 0, 0)];
+        assert!(LogFile::check_versions(&newer_major, VersionPolicy::BestEffort).is_err());
+    }
+
+    #[test]
+    fn load_refuses_before_parsing() {
+        let template = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../templates/logfile.ron");
+
+        assert!(matches!(
+            LogFile::try_from(template),
+            Err(error::LogFileError::IncompatibleVersion { game_index: 0, .. })
+        ));
+    }
+}
@@ -1,8 +1,13 @@
-use crate::{Event, Quarter, Team, error};
-use serde::Deserialize;
+use crate::{
+    DistanceBand, Down, DownAndDistance, Drive, DriveEnd, Event, FieldGoalBand, FieldGoalResult,
+    PartialDrive, Play, Quarter, ScorePoints, Scoreboard, Side, Team, TeamPenalty, TeamScore,
+    TurnoverKind, error,
+    stats::{self, Conversion, ConversionCount, Summary},
+};
+use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Game {
     pub version: semver::Version,
     pub flags: Vec<Flags>,
@@ -27,263 +32,690 @@ impl Game {
         let mut teams = vec![];
 
         self.events.iter().for_each(|event| {
-            if let Ok(team) = event.team() {
-                if !ignore.contains(&team) && !teams.contains(&team) {
-                    teams.push(team)
-                }
+            if let Ok(team) = event.team()
+                && !ignore.contains(&team)
+                && !teams.contains(&team)
+            {
+                teams.push(team)
             }
         });
 
-        if teams.len() == 2 || ignore.len() != 0 {
+        if teams.len() == 2 || !ignore.is_empty() {
             Ok(teams)
         } else {
             Err(error::TeamsError::NumberFound(teams.len()))
         }
     }
 
-    pub fn deltas(&self, team: Team) -> Vec<i8> {
-        let events: Vec<Event> = self
-            .team_events(team)
+    /// Every team present in the events, regardless of `Flags::IgnoreTeam`.
+    fn present_teams(&self) -> Vec<Team> {
+        let mut teams = vec![];
+
+        self.events.iter().for_each(|event| {
+            if let Ok(team) = event.team()
+                && !teams.contains(&team)
+            {
+                teams.push(team)
+            }
+        });
+
+        teams
+    }
+
+    /// Splits the events into possessions, in order.
+    ///
+    /// A drive starts at each `Kickoff` or `Turnover`, and ends at the next one,
+    /// at halftime or an overtime period, or at the end of the log.
+    /// Events that cannot be attributed to either team are left out.
+    pub fn drives(&self) -> Vec<Drive> {
+        let teams = self.present_teams();
+        let mut drives: Vec<Drive> = vec![];
+        let mut quarter = Quarter::First;
+        let mut partial: Option<PartialDrive> = None;
+
+        for (idx, event) in self.events.iter().enumerate() {
+            match event {
+                Event::Kickoff(team, ..)
+                | Event::Turnover(team, _)
+                | Event::Punt { offence: team, .. } => {
+                    let end = match event {
+                        Event::Turnover(_, kind) => DriveEnd::Turnover(kind.to_owned()),
+                        Event::Punt { .. } => DriveEnd::Punt,
+                        _ => DriveEnd::Turnover(None),
+                    };
+
+                    if let Some(previous) = partial.take() {
+                        drives.extend(previous.finish(end, Some(team), &teams))
+                    }
+
+                    partial = Some(PartialDrive {
+                        offence: Some(team.to_owned()),
+                        start: idx,
+                        quarter: quarter.to_owned(),
+                        events: vec![event.to_owned()],
+                    })
+                }
+                Event::Quarter(next) => {
+                    quarter = next.to_owned();
+
+                    if let Quarter::Third | Quarter::Overtime(_) = next {
+                        if let Some(previous) = partial.take() {
+                            drives.extend(previous.finish(DriveEnd::QuarterEnd, None, &teams))
+                        }
+                    } else if let Some(current) = partial.as_mut() {
+                        current.events.push(event.to_owned())
+                    }
+                }
+                _ => partial
+                    .get_or_insert_with(|| PartialDrive {
+                        offence: None,
+                        start: idx,
+                        quarter: quarter.to_owned(),
+                        events: vec![],
+                    })
+                    .events
+                    .push(event.to_owned()),
+            }
+        }
+
+        if let Some(last) = partial {
+            drives.extend(last.finish(DriveEnd::GameEnd, None, &teams))
+        }
+
+        drives
+    }
+
+    /// The quarter in effect at each event, indexed like `events`.
+    pub fn event_quarters(&self) -> Vec<Quarter> {
+        let mut quarter = Quarter::First;
+
+        self.events
             .iter()
-            .filter_map(|event| {
-                if let Event::Quarter(_) = event {
-                    None
-                } else {
-                    Some(event.to_owned())
+            .map(|event| {
+                if let Event::Quarter(next) = event {
+                    quarter = next.to_owned()
                 }
+
+                quarter.to_owned()
             })
-            .collect();
-        let len = events.len() - 1;
-        let mut idx: usize = 0;
+            .collect()
+    }
+
+    /// Attributes every score to a team, in order.
+    /// See `Event::Score` for how the team is decided.
+    pub fn team_scores(&self) -> Vec<TeamScore> {
+        let teams = self.present_teams();
+        let quarters = self.event_quarters();
+
+        self.drives()
+            .iter()
+            .flat_map(|drive| {
+                let defence = teams.iter().find(|team| **team != drive.offence);
+
+                drive
+                    .events
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, event)| {
+                        let (points, team) = match event {
+                            Event::Score(points, Some(team)) => (points, team),
+                            Event::Score(points @ ScorePoints::Safety, None) => (points, defence?),
+                            Event::Score(points, None) => (points, &drive.offence),
+                            _ => return None,
+                        };
+
+                        Some(TeamScore {
+                            event_index: drive.start + idx,
+                            quarter: quarters[drive.start + idx].to_owned(),
+                            team: team.to_owned(),
+                            points: points.to_owned(),
+                        })
+                    })
+                    .collect::<Vec<TeamScore>>()
+            })
+            .collect()
+    }
+
+    /// Returns the running score, or `None` if the game is `Flags::IgnoreScore`.
+    pub fn scoreboard(&self) -> Option<Scoreboard> {
+        if self.flags.contains(&Flags::IgnoreScore) {
+            return None;
+        }
+
+        Some(Scoreboard::new(
+            &self.present_teams(),
+            self.team_scores(),
+            self.events.len(),
+        ))
+    }
+
+    /// Returns the drives that `team` was on offence for.
+    pub fn team_drives(&self, team: Team) -> Vec<Drive> {
+        self.drives()
+            .into_iter()
+            .filter(|drive| drive.offence == team)
+            .collect()
+    }
+
+    /// The change in yards-to-go between each of `team`'s consecutive plays.
+    /// The play ending each drive is measured against the possession change.
+    pub fn deltas(&self, team: Team) -> Vec<i8> {
         let mut deltas: Vec<i8> = vec![];
 
-        while idx < len {
-            if let Some(value) = events[idx].delta(&events[idx + 1]) {
-                deltas.push(value);
+        for drive in self.team_drives(team) {
+            let mut events: Vec<&Event> = drive
+                .events
+                .iter()
+                .filter(|event| !matches!(event, Event::Quarter(_)))
+                .collect();
+
+            if let Some(next @ (Event::Kickoff(..) | Event::Punt { .. } | Event::Turnover(..))) =
+                self.events.get(drive.end_index())
+            {
+                events.push(next)
             }
 
-            idx += 1
+            events.windows(2).for_each(|pair| {
+                if let Some(value) = pair[0].delta(pair[1]) {
+                    deltas.push(value)
+                }
+            });
         }
 
         deltas
     }
 
-    pub fn team_plays(&self, team: Team) -> usize {
-        self.team_events(team)
-            .iter()
-            .filter_map(|event| {
-                if let Event::Play(_) = event {
-                    Some(event)
-                } else {
-                    None
+    /// Each of `team`'s plays, with the change in yards-to-go it made if known.
+    /// Plays straight after a penalty are not measured.
+    pub fn play_deltas(&self, team: Team) -> Vec<(Play, Option<i8>)> {
+        let mut plays = vec![];
+
+        for drive in self.team_drives(team) {
+            let events: Vec<&Event> = drive
+                .events
+                .iter()
+                .filter(|event| !matches!(event, Event::Quarter(_)))
+                .collect();
+
+            for (idx, event) in events.iter().enumerate() {
+                if let Event::Play(play) = event {
+                    let delta = match idx {
+                        0 => None,
+                        _ => events[idx - 1].delta(event),
+                    };
+
+                    plays.push((play.to_owned(), delta))
+                }
+            }
+        }
+
+        plays
+    }
+
+    /// Each of `team`'s plays with the down and distance it was faced on, drive by drive.
+    ///
+    /// Plays record the down and distance they leave the offence on, so those
+    /// faced are the ones left by the play before. Automatic first downs from
+    /// penalties start a new series, while other accepted penalties hide the
+    /// distance until the next play. A play with no down noted hides the down
+    /// the one after it was faced on.
+    pub fn faced_plays(&self, team: Team) -> Vec<Vec<(DownAndDistance, Play)>> {
+        let mut drives = vec![];
+
+        for drive in self.team_drives(team) {
+            let mut plays = vec![];
+            let mut facing = DownAndDistance::first_and_ten();
+
+            for event in &drive.events {
+                match event {
+                    Event::Play(play) => {
+                        plays.push((facing, play.to_owned()));
+
+                        facing = DownAndDistance {
+                            down: play.down.to_owned(),
+                            terrain: play.terrain.to_owned(),
+                        }
+                    }
+                    Event::Penalty(_, Some(details)) if details.automatic_first_down => {
+                        facing = DownAndDistance::first_and_ten()
+                    }
+                    Event::Penalty(_, Some(details)) if !details.ruling.is_accepted() => (),
+                    Event::Penalty(..) => facing.terrain = None,
+                    _ => (),
+                }
+            }
+
+            drives.push(plays)
+        }
+
+        drives
+    }
+
+    /// Each of `team`'s plays with the down and distance it was faced on,
+    /// and the change in yards-to-go it made if known.
+    /// See `Game::faced_plays` and `Game::play_deltas`.
+    pub fn faced_deltas(&self, team: Team) -> Vec<(DownAndDistance, Play, Option<i8>)> {
+        // Both run over the same plays, in the same order.
+        self.faced_plays(team.to_owned())
+            .into_iter()
+            .flatten()
+            .zip(self.play_deltas(team))
+            .map(|((faced, play), (_, delta))| (faced, play, delta))
+            .collect()
+    }
+
+    /// Every play `team` ran on third or fourth down, and whether it converted.
+    ///
+    /// A play converted if it scored a touchdown, or left the offence on first
+    /// down without giving up the ball straight after. Plays with no down noted
+    /// are left out, unless they scored.
+    pub fn conversions(&self, team: Team) -> Vec<Conversion> {
+        let mut conversions = vec![];
+
+        for (drive, plays) in self
+            .team_drives(team.to_owned())
+            .into_iter()
+            .zip(self.faced_plays(team))
+        {
+            let events: Vec<&Event> = drive
+                .events
+                .iter()
+                .filter(|event| !matches!(event, Event::Quarter(_)))
+                .collect();
+            let positions = events
+                .iter()
+                .enumerate()
+                .filter(|(_, event)| matches!(event, Event::Play(_)))
+                .map(|(idx, _)| idx);
+
+            for (idx, (faced, play)) in positions.zip(plays) {
+                if !matches!(faced.down, Some(Down::Third | Down::Fourth)) {
+                    continue;
+                }
+
+                let converted = match events.get(idx + 1) {
+                    Some(Event::Score(ScorePoints::Touchdown, _)) => Some(true),
+                    Some(Event::Score(..) | Event::FieldGoalAttempt { .. }) => Some(false),
+                    // The log notes the new offence's down after a turnover on downs.
+                    None if matches!(
+                        self.events.get(drive.end_index()),
+                        Some(Event::Kickoff(..) | Event::Punt { .. } | Event::Turnover(..))
+                    ) =>
+                    {
+                        play.down.as_ref().map(|_| false)
+                    }
+                    _ => play.down.as_ref().map(|down| *down == Down::First),
+                };
+
+                if let Some(converted) = converted {
+                    conversions.push(Conversion { faced, converted })
                 }
+            }
+        }
+
+        conversions
+    }
+
+    /// `team`'s third and fourth down conversions, by down and distance.
+    pub fn conversion_counts(&self, team: Team) -> Vec<ConversionCount> {
+        let mut counts = ConversionCount::empty();
+
+        ConversionCount::add(&mut counts, &self.conversions(team));
+
+        counts
+    }
+
+    /// The down `team` was facing on each play that gained a new first down.
+    ///
+    /// A play gained one if it left the offence on first down.
+    /// Automatic first downs from penalties start a new series without counting.
+    pub fn first_downs(&self, team: Team) -> Vec<Down> {
+        self.faced_plays(team)
+            .into_iter()
+            .flatten()
+            .filter_map(|(facing, play)| match play.down {
+                Some(Down::First) => facing.down,
+                _ => None,
             })
-            .collect::<Vec<&Event>>()
-            .len()
+            .collect()
+    }
+
+    pub fn team_plays(&self, team: Team) -> usize {
+        self.team_drives(team)
+            .iter()
+            .map(|drive| drive.plays().len())
+            .sum()
+    }
+
+    /// The number of plays `team` ran in each regulation quarter.
+    pub fn quarterly_plays(&self, team: Team) -> Vec<usize> {
+        Quarter::iter()
+            .map(|quarter| self.get_period(quarter))
+            .filter(|period| !period.is_overtime())
+            .map(|period| period.team_plays(team.to_owned()))
+            .collect()
     }
 
     /// The average number of plays in a quarter.
-    pub fn avg_plays_per_quarter(&self, team: Team) -> f32 {
-        let periods: Vec<Period> = Quarter::iter()
-            .filter_map(|quarter| Some(self.to_owned().get_period(quarter.to_owned())).to_owned())
-            .collect();
+    pub fn avg_plays_per_quarter(&self, team: Team) -> Option<f32> {
+        Some(self.plays_per_quarter_summary(team)?.mean)
+    }
+
+    pub fn avg_delta(&self, team: Team) -> Option<f32> {
+        Some(self.delta_summary(team)?.mean)
+    }
 
-        let quarterly_avgs: Vec<f32> = periods
+    pub fn avg_gain(&self, team: Team) -> Option<f32> {
+        Some(self.gain_summary(team)?.mean)
+    }
+
+    pub fn avg_loss(&self, team: Team) -> Option<f32> {
+        Some(self.loss_summary(team)?.mean)
+    }
+
+    pub fn plays_per_quarter_summary(&self, team: Team) -> Option<Summary> {
+        let plays: Vec<f32> = self
+            .quarterly_plays(team)
             .iter()
-            .filter_map(|period| {
-                if !period.is_overtime() {
-                    Some(period.team_plays(team.to_owned()) as f32)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<f32>>();
+            .map(|plays| *plays as f32)
+            .collect();
 
-        quarterly_avgs.iter().sum::<f32>() / quarterly_avgs.len() as f32
+        Summary::new(&plays)
     }
 
-    pub fn avg_delta(&self, team: Team) -> f32 {
-        let deltas = self.deltas(team);
+    pub fn delta_summary(&self, team: Team) -> Option<Summary> {
+        Summary::from_counts(&self.deltas(team))
+    }
 
-        // Summation doesn't like directly returning f32 from i8.
-        deltas.iter().sum::<i8>() as f32 / deltas.len() as f32
+    /// Summarises the positive deltas of `team`.
+    pub fn gain_summary(&self, team: Team) -> Option<Summary> {
+        Summary::from_counts(&stats::gains(&self.deltas(team)))
     }
 
-    pub fn avg_gain(&self, team: Team) -> f32 {
-        let deltas: Vec<u8> = self
-            .deltas(team)
+    /// Summarises the negative deltas of `team`.
+    pub fn loss_summary(&self, team: Team) -> Option<Summary> {
+        Summary::from_counts(&stats::losses(&self.deltas(team)))
+    }
+
+    /// Average yards from its own goal line that `team` started drives at,
+    /// `None` if no starting positions are known.
+    pub fn avg_start_position(&self, team: Team) -> Option<f32> {
+        let starts: Vec<u8> = self
+            .team_drives(team)
             .iter()
-            .filter_map(|value| {
-                if value.is_positive() {
-                    Some(value.to_owned() as u8)
-                } else {
-                    None
-                }
-            })
+            .filter_map(|drive| drive.start_position())
             .collect();
 
-        // Summation doesn't like directly returning f32 from u8.
-        deltas.iter().sum::<u8>() as f32 / deltas.len() as f32
+        if starts.is_empty() {
+            return None;
+        }
+
+        Some(starts.iter().map(|start| *start as f32).sum::<f32>() / starts.len() as f32)
     }
 
-    pub fn avg_loss(&self, team: Team) -> f32 {
-        let deltas: Vec<i8> = self
-            .deltas(team)
+    /// The number of drives in which `team` got inside the opponent's 20.
+    pub fn red_zone_trips(&self, team: Team) -> usize {
+        self.team_drives(team)
             .iter()
-            .filter_map(|value| {
-                if value.is_negative() {
-                    Some(value.to_owned())
-                } else {
-                    None
-                }
+            .filter(|drive| drive.reached_red_zone())
+            .count()
+    }
+
+    /// Average yards gained by `team`'s punts after returns,
+    /// `None` if none of its punts had a distance noted.
+    pub fn net_punt_average(&self, team: Team) -> Option<f32> {
+        let nets: Vec<i16> = self
+            .team_drives(team)
+            .iter()
+            .filter(|drive| drive.end == DriveEnd::Punt)
+            .filter_map(|drive| match self.events.get(drive.end_index()) {
+                Some(Event::Punt {
+                    distance: Some(distance),
+                    return_yards,
+                    ..
+                }) => Some(*distance as i16 - return_yards.unwrap_or(0) as i16),
+                _ => None,
             })
             .collect();
 
-        deltas.iter().sum::<i8>() as f32 / deltas.len() as f32
+        if nets.is_empty() {
+            return None;
+        }
+
+        Some(nets.iter().sum::<i16>() as f32 / nets.len() as f32)
     }
 
-    pub fn penalties(&self, team: Team) -> usize {
-        self.team_events(team)
+    /// `team`'s field goal attempts, grouped by distance.
+    pub fn field_goals_by_distance(&self, team: Team) -> Vec<FieldGoalBand> {
+        let mut bands: Vec<FieldGoalBand> = DistanceBand::iter()
+            .map(|band| FieldGoalBand {
+                band,
+                made: 0,
+                attempts: 0,
+            })
+            .collect();
+
+        for drive in self.team_drives(team) {
+            for (distance, result) in drive.field_goal_attempts() {
+                let band = DistanceBand::from_distance(distance);
+
+                if let Some(totals) = bands.iter_mut().find(|totals| totals.band == band) {
+                    totals.attempts += 1;
+
+                    if *result == FieldGoalResult::Good {
+                        totals.made += 1
+                    }
+                }
+            }
+        }
+
+        bands
+    }
+
+    /// Takeaways minus giveaways for `team`, counting only interceptions and fumbles.
+    pub fn turnover_margin(&self, team: Team) -> i16 {
+        let teams = self.present_teams();
+
+        self.events
             .iter()
-            .filter_map(|event| {
-                if let Event::Penalty(_) = event {
-                    Some(event.to_owned())
-                } else {
-                    None
+            .map(|event| match event {
+                Event::Turnover(offence, Some(kind)) if kind.is_takeaway() => {
+                    if *offence == team {
+                        1
+                    } else if teams.contains(&team) {
+                        -1
+                    } else {
+                        0
+                    }
                 }
+                _ => 0,
             })
-            .collect::<Vec<Event>>()
-            .len()
+            .sum()
     }
 
-    pub fn get_period(&self, quarter: Quarter) -> Period {
-        let mut record = false;
+    /// The number of turnovers `team` gave up of each kind, from how its drives ended.
+    pub fn turnovers_by_kind(&self, team: Team) -> Vec<(TurnoverKind, usize)> {
+        let drives = self.team_drives(team);
 
-        Period {
-            period: quarter.to_owned(),
-            events: self
-                .events
-                .iter()
-                .filter_map(|event| {
-                    if let Event::Quarter(_) = event {
-                        record = Event::Quarter(quarter.to_owned()) == *event;
-                    }
+        TurnoverKind::iter()
+            .map(|kind| {
+                let count = drives
+                    .iter()
+                    .filter(|drive| drive.end == DriveEnd::Turnover(Some(kind.to_owned())))
+                    .count();
 
-                    if record {
-                        return Some(event.to_owned());
-                    }
+                (kind, count)
+            })
+            .collect()
+    }
 
-                    None
-                })
-                .collect::<Vec<Event>>(),
-        }
+    /// Attributes every penalty to the team that committed it, in order.
+    ///
+    /// An explicit team wins, then the side of the ball relative to the drive's
+    /// offence. Penalties without either are counted against the offence.
+    pub fn team_penalties(&self) -> Vec<TeamPenalty> {
+        let teams = self.present_teams();
+
+        self.drives()
+            .iter()
+            .flat_map(|drive| {
+                let defence = teams.iter().find(|team| **team != drive.offence);
+                let mut down = Down::First;
+
+                drive
+                    .events
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(idx, event)| {
+                        let (distance, details) = match event {
+                            Event::Play(Play {
+                                down: Some(next), ..
+                            }) => {
+                                down = next.to_owned();
+                                return None;
+                            }
+                            Event::Penalty(distance, details) => {
+                                (distance, details.to_owned().unwrap_or_default())
+                            }
+                            _ => return None,
+                        };
+
+                        let (team, side) = match (details.team, details.side) {
+                            (Some(team), Some(side)) => (team, side),
+                            (Some(team), None) if team == drive.offence => (team, Side::Offence),
+                            (Some(team), None) => (team, Side::Defence),
+                            (None, Some(Side::Defence)) => (defence?.to_owned(), Side::Defence),
+                            (None, _) => (drive.offence.to_owned(), Side::Offence),
+                        };
+
+                        Some(TeamPenalty {
+                            event_index: drive.start + idx,
+                            team,
+                            side,
+                            down: down.to_owned(),
+                            distance: distance.to_owned(),
+                            ruling: details.ruling,
+                            automatic_first_down: details.automatic_first_down,
+                        })
+                    })
+                    .collect::<Vec<TeamPenalty>>()
+            })
+            .collect()
     }
 
-    pub fn team_events(&self, team: Team) -> Vec<Event> {
-        let mut events: Vec<Event> = vec![];
-        let mut first = true;
-        let mut record: bool = true;
+    /// The number of penalties committed by `team`, whatever the ruling.
+    pub fn penalties(&self, team: Team) -> usize {
+        self.team_penalties()
+            .iter()
+            .filter(|penalty| penalty.team == team)
+            .count()
+    }
 
-        self.events.iter().for_each(|event| {
-            if let Event::Kickoff(_) | Event::Turnover(_) = event {
-                record = {
-                    if team == event.team().unwrap() {
-                        // Wipe events vec if the start of quarter was opposition
-                        // on offence.
-                        if first {
-                            events = vec![];
-                        }
+    /// Yards of accepted penalties enforced against `team`.
+    pub fn penalty_yards(&self, team: Team) -> u16 {
+        self.team_penalties()
+            .iter()
+            .filter(|penalty| penalty.team == team)
+            .filter_map(|penalty| penalty.yards())
+            .map(|yards| yards as u16)
+            .sum()
+    }
 
-                        true
-                    } else {
-                        events.push(event.to_owned());
-                        false
+    /// The number of penalties committed by `team` on each down.
+    pub fn penalties_by_down(&self, team: Team) -> Vec<(Down, usize)> {
+        let penalties: Vec<TeamPenalty> = self
+            .team_penalties()
+            .into_iter()
+            .filter(|penalty| penalty.team == team)
+            .collect();
+
+        [Down::First, Down::Second, Down::Third, Down::Fourth]
+            .into_iter()
+            .map(|down| {
+                let count = penalties
+                    .iter()
+                    .filter(|penalty| penalty.down == down)
+                    .count();
+
+                (down, count)
+            })
+            .collect()
+    }
+
+    /// Returns the parts of every drive that took place during `quarter`.
+    pub fn get_period(&self, quarter: Quarter) -> Period {
+        let drives = self
+            .drives()
+            .into_iter()
+            .filter_map(|mut drive| {
+                let mut current = drive.quarter.to_owned();
+
+                drive.events.retain(|event| {
+                    if let Event::Quarter(next) = event {
+                        current = next.to_owned();
                     }
-                };
 
-                first = false;
-            }
+                    current == quarter
+                });
 
-            if record {
-                events.push(event.to_owned());
-            }
-        });
+                if drive.events.is_empty() {
+                    None
+                } else {
+                    Some(drive)
+                }
+            })
+            .collect();
 
-        // If already handled or assumption override applicable
-        events
+        Period {
+            period: quarter,
+            drives,
+        }
+    }
+
+    /// Returns the events of every drive `team` was on offence for.
+    pub fn team_events(&self, team: Team) -> Vec<Event> {
+        self.team_drives(team)
+            .into_iter()
+            .flat_map(|drive| drive.events)
+            .collect()
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Period {
     period: Quarter,
-    events: Vec<Event>,
+    drives: Vec<Drive>,
 }
 
 impl Period {
     pub fn team_events(&self, team: Team) -> Vec<Event> {
-        let mut events: Vec<Event> = vec![];
-        let mut first = true;
-        let mut record: bool = true;
-
-        self.events.iter().for_each(|event| {
-            if let Event::Kickoff(_) | Event::Turnover(_) = event {
-                record = {
-                    if team == event.team().unwrap() {
-                        // Wipe events vec if the start of quarter was opposition
-                        // on offence.
-                        if first {
-                            events = vec![];
-                        }
-
-                        true
-                    } else {
-                        events.push(event.to_owned());
-                        false
-                    }
-                };
-
-                first = false;
-            }
-
-            if record {
-                events.push(event.to_owned());
-            }
-        });
-
-        events
+        self.drives
+            .iter()
+            .filter(|drive| drive.offence == team)
+            .flat_map(|drive| drive.events.to_owned())
+            .collect()
     }
 
     pub fn team_plays(&self, team: Team) -> usize {
-        self.team_events(team)
+        self.drives
             .iter()
-            .filter_map(|event| {
-                if let Event::Play(_) = event {
-                    Some(event)
-                } else {
-                    None
-                }
-            })
-            .collect::<Vec<&Event>>()
-            .len()
+            .filter(|drive| drive.offence == team)
+            .map(|drive| drive.plays().len())
+            .sum()
     }
 
     pub fn is_overtime(&self) -> bool {
-        if let Quarter::Overtime(_) = self.period {
-            true
-        } else {
-            false
-        }
+        matches!(self.period, Quarter::Overtime(_))
     }
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub enum Flags {
     IgnoreActions,
     IgnoreTeam(Team),
     IgnoreScore,
     Interval(u8),
-    SheerDumbFuckingLuck
+    SheerDumbFuckingLuck,
 }
 
 /*
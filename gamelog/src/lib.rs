@@ -1,21 +1,49 @@
 mod action;
-mod error;
+mod drive;
+pub mod error;
 #[allow(deprecated)]
 mod event;
 mod file;
 mod game;
+pub mod migrate;
+pub mod model;
+mod penalty;
 mod period;
 mod play;
+pub mod ranking;
+mod scoreboard;
+pub mod source;
+mod special_teams;
+pub mod stats;
 mod terrain;
+#[cfg(test)]
+pub(crate) mod testing;
+pub mod validate;
 
-#[allow(unused)]
-pub const MIN_VER: semver::Version = semver::Version::new(0, 7, 0);
+pub const MIN_VER: semver::Version = semver::Version::new(0, 8, 0);
+
+/// Versions sharing the schema of `MIN_VER`, i.e. `~MIN_VER`.
+pub fn supported_versions() -> semver::VersionReq {
+    semver::VersionReq {
+        comparators: vec![semver::Comparator {
+            op: semver::Op::Tilde,
+            major: MIN_VER.major,
+            minor: Some(MIN_VER.minor),
+            patch: Some(MIN_VER.patch),
+            pre: semver::Prerelease::EMPTY,
+        }],
+    }
+}
 
 // I'm lazy.
 pub use action::*;
+pub use drive::*;
 pub use event::*;
 pub use file::*;
 pub use game::*;
+pub use penalty::*;
 pub use period::*;
 pub use play::*;
+pub use scoreboard::*;
+pub use special_teams::*;
 pub use terrain::*;
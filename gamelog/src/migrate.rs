@@ -0,0 +1,501 @@
+//! Upgrades older logfile documents to the current schema.
+//!
+//! Each schema the format has gone through gets a variant in `Document`, and
+//! `Document::upgrade` moves a document exactly one schema forward. Migrating
+//! is just upgrading until the document is current, so supporting a new format
+//! bump only needs a new variant and a single new step.
+
+use crate::{Event, Flags, Game, LogFile, error};
+use serde::Deserialize;
+use std::path::PathBuf;
+
+/// Document shapes from before `Event::Quarter` markers replaced periods.
+///
+/// Frozen as they were in 0.5, so later changes to the current types
+/// never change what a 0.5 logfile means.
+pub mod v0_5 {
+    use crate::{Action, Down, ScorePoints, Team, TerrainState};
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, Clone, PartialEq)]
+    pub struct Game {
+        pub version: semver::Version,
+        pub flags: Vec<Flags>,
+        pub periods: Vec<Period>,
+    }
+
+    #[derive(Debug, Deserialize, Clone, PartialEq)]
+    pub struct Period {
+        pub start: Quarter,
+        pub end: Option<Quarter>,
+        pub events: Vec<Event>,
+    }
+
+    #[derive(Debug, Deserialize, Clone, PartialEq)]
+    pub enum Event {
+        Kickoff(Team),
+        Play(Play),
+        Turnover(Team),
+        Penalty(TerrainState),
+        Score(ScorePoints),
+        Quarter(Quarter),
+    }
+
+    #[derive(Debug, Deserialize, Clone, PartialEq)]
+    pub struct Play {
+        pub action: Action,
+        pub down: Option<Down>,
+        pub terrain: Option<TerrainState>,
+    }
+
+    #[derive(Debug, Deserialize, Clone, PartialEq)]
+    pub enum Quarter {
+        First,
+        Second,
+        Third,
+        Fourth,
+        Overtime(u8),
+    }
+
+    #[derive(Debug, Deserialize, Clone, PartialEq)]
+    pub enum Flags {
+        IgnoreActions,
+        IgnoreTeam(Team),
+        IgnoreScore,
+        Interval(u8),
+        SheerDumbFuckingLuck,
+    }
+
+    impl From<Event> for super::v0_7::Event {
+        fn from(event: Event) -> Self {
+            match event {
+                Event::Kickoff(team) => Self::Kickoff(team, None),
+                Event::Play(play) => Self::Play(super::v0_7::Play {
+                    action: play.action,
+                    down: play.down,
+                    terrain: play.terrain,
+                    position: None,
+                }),
+                Event::Turnover(team) => Self::Turnover(team, None),
+                Event::Penalty(terrain) => Self::Penalty(terrain, None),
+                Event::Score(points) => Self::Score(points, None),
+                Event::Quarter(quarter) => Self::Quarter(quarter),
+            }
+        }
+    }
+
+    impl From<Quarter> for crate::Quarter {
+        fn from(quarter: Quarter) -> Self {
+            match quarter {
+                Quarter::First => Self::First,
+                Quarter::Second => Self::Second,
+                Quarter::Third => Self::Third,
+                Quarter::Fourth => Self::Fourth,
+                Quarter::Overtime(period) => Self::Overtime(period),
+            }
+        }
+    }
+
+    impl From<Flags> for crate::Flags {
+        fn from(flags: Flags) -> Self {
+            match flags {
+                Flags::IgnoreActions => Self::IgnoreActions,
+                Flags::IgnoreTeam(team) => Self::IgnoreTeam(team),
+                Flags::IgnoreScore => Self::IgnoreScore,
+                Flags::Interval(interval) => Self::Interval(interval),
+                Flags::SheerDumbFuckingLuck => Self::SheerDumbFuckingLuck,
+            }
+        }
+    }
+}
+
+/// Document shapes from before punts, field goal attempts and return yards.
+///
+/// Frozen as they were in 0.7. `Quarter` and `Flags` are unchanged since 0.5.
+pub mod v0_7 {
+    use super::v0_5::{Flags, Quarter};
+    use crate::{
+        Action, Down, Ruling, ScorePoints, Side, Team, TerrainState, TurnoverKind, YardLine,
+    };
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize, Clone, PartialEq)]
+    pub struct Game {
+        pub version: semver::Version,
+        pub flags: Vec<Flags>,
+        pub events: Vec<Event>,
+    }
+
+    #[derive(Debug, Deserialize, Clone, PartialEq)]
+    pub enum Event {
+        Kickoff(Team, #[serde(default)] Option<YardLine>),
+        Play(Play),
+        Turnover(Team, #[serde(default)] Option<TurnoverKind>),
+        Penalty(TerrainState, #[serde(default)] Option<Penalty>),
+        Score(ScorePoints, #[serde(default)] Option<Team>),
+        Quarter(Quarter),
+    }
+
+    #[derive(Debug, Deserialize, Clone, PartialEq)]
+    pub struct Play {
+        pub action: Action,
+        pub down: Option<Down>,
+        pub terrain: Option<TerrainState>,
+        #[serde(default)]
+        pub position: Option<YardLine>,
+    }
+
+    #[derive(Debug, Deserialize, Clone, PartialEq)]
+    pub struct Penalty {
+        #[serde(default)]
+        pub team: Option<Team>,
+        #[serde(default)]
+        pub side: Option<Side>,
+        #[serde(default)]
+        pub ruling: Ruling,
+        #[serde(default)]
+        pub automatic_first_down: bool,
+    }
+
+    impl From<Event> for crate::Event {
+        fn from(event: Event) -> Self {
+            match event {
+                Event::Kickoff(team, position) => Self::Kickoff(team, position, None),
+                Event::Play(play) => Self::Play(play.into()),
+                Event::Turnover(team, kind) => Self::Turnover(team, kind),
+                Event::Penalty(terrain, penalty) => Self::Penalty(terrain, penalty.map(Into::into)),
+                Event::Score(points, team) => Self::Score(points, team),
+                Event::Quarter(quarter) => Self::Quarter(quarter.into()),
+            }
+        }
+    }
+
+    impl From<Play> for crate::Play {
+        fn from(play: Play) -> Self {
+            Self {
+                action: play.action,
+                down: play.down,
+                terrain: play.terrain,
+                position: play.position,
+            }
+        }
+    }
+
+    impl From<Penalty> for crate::Penalty {
+        fn from(penalty: Penalty) -> Self {
+            Self {
+                team: penalty.team,
+                side: penalty.side,
+                ruling: penalty.ruling,
+                automatic_first_down: penalty.automatic_first_down,
+            }
+        }
+    }
+}
+
+/// Only reads the version out of each game, ignoring whatever shape the rest is.
+#[derive(Debug, Deserialize)]
+#[serde(rename = "Game")]
+struct VersionProbe {
+    version: semver::Version,
+}
+
+/// Every schema a logfile has been written in, oldest first.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Document {
+    V0_5(Vec<v0_5::Game>),
+    V0_7(Vec<v0_7::Game>),
+    /// Adds `Punt` and `FieldGoalAttempt` events, and return yards on kickoffs.
+    V0_8(LogFile),
+}
+
+impl Document {
+    /// Parses `src` as whichever schema its games' versions declare.
+    pub fn parse(src: &str) -> Result<Self, error::MigrateError> {
+        let version = detect_version(src)?;
+
+        if version.major == 0 && (5..7).contains(&version.minor) {
+            Ok(Self::V0_5(parse(src)?))
+        } else if version.major == 0 && version.minor == 7 {
+            Ok(Self::V0_7(parse(src)?))
+        } else if version.major == 0 && version.minor == 8 {
+            Ok(Self::V0_8(parse(src)?))
+        } else {
+            Err(error::MigrateError::UnsupportedVersion(version))
+        }
+    }
+
+    /// Returns `true` if `self` is in the current schema.
+    pub fn is_current(&self) -> bool {
+        matches!(self, Self::V0_8(_))
+    }
+
+    /// Moves `self` one schema forward. Current documents are returned as is.
+    ///
+    /// Upgraded games are written as the first version of their new schema,
+    /// whatever patch version they were before.
+    pub fn upgrade(self) -> Self {
+        match self {
+            Self::V0_5(games) => Self::V0_7(games.into_iter().map(v0_7::Game::from).collect()),
+            Self::V0_7(games) => Self::V0_8(LogFile(games.into_iter().map(Game::from).collect())),
+            Self::V0_8(_) => self,
+        }
+    }
+
+    /// Upgrades `self` step by step until it is in the current schema.
+    pub fn into_current(mut self) -> LogFile {
+        while !self.is_current() {
+            self = self.upgrade()
+        }
+
+        match self {
+            Self::V0_8(log) => log,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl From<v0_5::Game> for v0_7::Game {
+    /// Flattens periods into one event list, opening each with a quarter marker.
+    ///
+    /// Periods spanning several quarters never recorded where the later quarters
+    /// began, so only the starting quarter gets a marker.
+    fn from(game: v0_5::Game) -> Self {
+        Self {
+            version: semver::Version::new(0, 7, 0),
+            flags: game.flags,
+            events: game
+                .periods
+                .into_iter()
+                .flat_map(|period| {
+                    std::iter::once(v0_7::Event::Quarter(period.start))
+                        .chain(period.events.into_iter().map(v0_7::Event::from))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<v0_7::Game> for Game {
+    /// Every 0.7 event is still valid in 0.8.
+    fn from(game: v0_7::Game) -> Self {
+        Self {
+            version: crate::MIN_VER,
+            flags: game.flags.into_iter().map(Flags::from).collect(),
+            events: game.events.into_iter().map(Event::from).collect(),
+        }
+    }
+}
+
+fn parse<T: for<'a> Deserialize<'a>>(src: &str) -> Result<T, error::MigrateError> {
+    match crate::file::ron_options().from_str(src) {
+        Ok(t) => Ok(t),
+        Err(err) => Err(error::MigrateError::RonSpannedError(err)),
+    }
+}
+
+/// Returns the version of each game in `src`, without parsing the rest of it.
+pub(crate) fn game_versions(src: &str) -> Result<Vec<semver::Version>, ron::error::SpannedError> {
+    let probes: Vec<VersionProbe> = crate::file::ron_options().from_str(src)?;
+
+    Ok(probes.into_iter().map(|probe| probe.version).collect())
+}
+
+/// Returns the version shared by every game in `src`.
+pub fn detect_version(src: &str) -> Result<semver::Version, error::MigrateError> {
+    let mut versions: Vec<semver::Version> = vec![];
+
+    match game_versions(src) {
+        Ok(found) => found,
+        Err(err) => return Err(error::MigrateError::RonSpannedError(err)),
+    }
+    .into_iter()
+    .for_each(|version| {
+        if !versions.contains(&version) {
+            versions.push(version)
+        }
+    });
+
+    // Patch versions never change the schema, so only they may differ.
+    match versions.first() {
+        None => Ok(crate::MIN_VER),
+        Some(first)
+            if versions
+                .iter()
+                .all(|v| v.major == first.major && v.minor == first.minor) =>
+        {
+            Ok(first.to_owned())
+        }
+        Some(_) => Err(error::MigrateError::MixedVersions(versions)),
+    }
+}
+
+/// Parses `src` in whatever schema it was written in and upgrades it to current.
+pub fn migrate(src: &str) -> Result<LogFile, error::MigrateError> {
+    Ok(Document::parse(src)?.into_current())
+}
+
+/// Reads the logfile at `path` and upgrades it to the current schema.
+pub fn migrate_file(path: PathBuf) -> Result<LogFile, error::MigrateError> {
+    match std::fs::read_to_string(path.as_path()) {
+        Ok(src) => migrate(&src),
+        Err(err) => Err(error::MigrateError::FailedToOpen(err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::path::PathBuf;
+
+    fn root() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..")
+    }
+
+    #[test]
+    fn detect_version() {
+        let template = std::fs::read_to_string(root().join("templates/logfile.ron")).unwrap();
+        let gamelog = std::fs::read_to_string(root().join("gamelog.ron")).unwrap();
+
+        assert!(migrate::detect_version(&template).unwrap() == semver::Version::new(0, 5, 0));
+        assert!(migrate::detect_version(&gamelog).unwrap() == semver::Version::new(0, 8, 0));
+    }
+
+    #[test]
+    fn mixed_versions() {
+        let src = r#"[
+            Game(version: "0.5.0", flags: [], periods: []),
+            Game(version: "0.7.0", flags: [], events: []),
+        ]"#;
+
+        assert!(matches!(
+            migrate::detect_version(src),
+            Err(error::MigrateError::MixedVersions(_))
+        ));
+    }
+
+    #[test]
+    fn v0_5() {
+        let log = migrate::migrate_file(root().join("templates/logfile.ron")).unwrap();
+
+        assert!(
+            log == LogFile(vec![Game {
+                version: crate::MIN_VER,
+                flags: vec![],
+                events: vec![
+                    Event::Quarter(Quarter::First),
+                    Event::Kickoff(Team::Nebraska, None, None),
+                    Event::Play(Play::default()),
+                    Event::Score(ScorePoints::FieldGoal, None),
+                ],
+            }])
+        );
+    }
+
+    #[test]
+    fn v0_5_shapes() {
+        let src = r#"[
+            Game(
+                version: "0.5.0",
+                flags: [IgnoreTeam(Iowa), IgnoreScore],
+                periods: [
+                    Period(
+                        start: Third,
+                        end: None,
+                        events: [
+                            Turnover(Iowa),
+                            Penalty(Yards(5)),
+                            Quarter(Overtime(1)),
+                            Score(Touchdown),
+                        ],
+                    ),
+                ],
+            ),
+        ]"#;
+
+        assert!(
+            migrate::migrate(src).unwrap().0[0]
+                == Game {
+                    version: crate::MIN_VER,
+                    flags: vec![Flags::IgnoreTeam(Team::Iowa), Flags::IgnoreScore],
+                    events: vec![
+                        Event::Quarter(Quarter::Third),
+                        Event::Turnover(Team::Iowa, None),
+                        Event::Penalty(TerrainState::Yards(5), None),
+                        Event::Quarter(Quarter::Overtime(1)),
+                        Event::Score(ScorePoints::Touchdown, None),
+                    ],
+                }
+        );
+    }
+
+    #[test]
+    fn v0_7() {
+        let src = r#"#![enable(implicit_some)]
+        #![enable(unwrap_newtypes)]
+        [
+            Game(
+                version: "0.7.2",
+                flags: [],
+                events: [
+                    Quarter(First),
+                    Kickoff(Nebraska, Own(25)),
+                    Penalty(Yards(5), Penalty(team: Iowa)),
+                    Score(Touchdown),
+                ],
+            ),
+        ]"#;
+
+        let document = migrate::Document::parse(src).unwrap();
+
+        assert!(matches!(document, migrate::Document::V0_7(_)));
+        assert!(matches!(
+            document.to_owned().upgrade(),
+            migrate::Document::V0_8(_)
+        ));
+        assert!(
+            document.into_current()
+                == LogFile(vec![Game {
+                    version: crate::MIN_VER,
+                    flags: vec![],
+                    events: vec![
+                        Event::Quarter(Quarter::First),
+                        Event::Kickoff(Team::Nebraska, Some(YardLine::Own(25)), None),
+                        Event::Penalty(
+                            TerrainState::Yards(5),
+                            Some(Penalty {
+                                team: Some(Team::Iowa),
+                                ..Default::default()
+                            })
+                        ),
+                        Event::Score(ScorePoints::Touchdown, None),
+                    ],
+                }])
+        );
+    }
+
+    #[test]
+    fn v0_7_rejects_v0_8_events() {
+        for event in [
+            "Punt(offence: Iowa)",
+            "FieldGoalAttempt(distance: 33, result: Good)",
+            "Kickoff(Iowa, Own(30), 20)",
+        ] {
+            let src = format!(
+                r#"[Game(version: "0.7.0", flags: [], events: [{}])]"#,
+                event
+            );
+
+            assert!(migrate::Document::parse(&src).is_err());
+        }
+    }
+
+    #[test]
+    fn current_is_untouched() {
+        let path = root().join("gamelog.ron");
+
+        assert!(
+            migrate::migrate_file(path.to_owned()).unwrap() == LogFile::try_from(path).unwrap()
+        );
+    }
+}
@@ -0,0 +1,4 @@
+//! Models fitted from the plays of a LogFile.
+
+pub mod expected_points;
+pub mod markov;
@@ -0,0 +1,555 @@
+use crate::{
+    Action, DistanceToGo, Down, DownAndDistance, DriveEnd, Event, Flags, Game, LogFile, Play,
+    Quarter, Team, TeamScore,
+};
+use std::{cmp::Reverse, fmt};
+use strum::EnumIter;
+
+/// Plays a state must be seen on before its own mean is trusted.
+pub const MIN_SAMPLES: usize = 5;
+
+/// Twenty yard bands of the field, from the offence's own goal line.
+#[derive(Debug, Clone, PartialEq, EnumIter)]
+pub enum FieldZone {
+    /// Inside the offence's own 20.
+    OwnDeep,
+    Own,
+    Midfield,
+    Opponent,
+    /// Inside the opponent's 20.
+    RedZone,
+}
+
+impl FieldZone {
+    /// Takes the yards from the offence's own goal line.
+    pub fn from_position(position: u8) -> Self {
+        match position {
+            0..=19 => Self::OwnDeep,
+            20..=39 => Self::Own,
+            40..=59 => Self::Midfield,
+            60..=79 => Self::Opponent,
+            _ => Self::RedZone,
+        }
+    }
+}
+
+/// The down, distance and field position an offence faced before a play.
+#[derive(Debug, Clone, PartialEq)]
+pub struct State {
+    pub down: Down,
+    /// `None` where the distance was not known, including at the goal line.
+    pub distance: Option<DistanceToGo>,
+    /// `None` where the field position was not known.
+    pub zone: Option<FieldZone>,
+}
+
+impl State {
+    /// Returns `None` where the down is unknown.
+    pub fn new(faced: &DownAndDistance, position: Option<u8>) -> Option<Self> {
+        Some(Self {
+            down: faced.down.to_owned()?,
+            distance: faced.distance(),
+            zone: position.map(FieldZone::from_position),
+        })
+    }
+
+    /// 1st & 10 at `position`, as a new offence takes over.
+    pub fn first_and_ten(position: Option<u8>) -> Self {
+        Self {
+            down: Down::First,
+            distance: Some(DistanceToGo::Long),
+            zone: position.map(FieldZone::from_position),
+        }
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.down)?;
+
+        if let Some(distance) = &self.distance {
+            write!(f, " & {:?}", distance)?;
+        }
+
+        if let Some(zone) = &self.zone {
+            write!(f, " at {:?}", zone)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The mean points of the next score from one state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateValue {
+    pub state: State,
+    /// Plays faced in the state.
+    pub samples: usize,
+    pub points: f32,
+}
+
+impl fmt::Display for StateValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {:+.2} over {} plays",
+            self.state, self.points, self.samples
+        )
+    }
+}
+
+/// A play, scored by `ExpectedPoints`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayValue {
+    pub play: Play,
+    /// `None` where the down faced was unknown.
+    pub before: Option<State>,
+    /// Expected points before the play.
+    pub expected: Option<f32>,
+    /// Expected points added by the play, EPA.
+    pub added: Option<f32>,
+}
+
+/// The expected points a team added calling one action.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallValue {
+    pub action: Action,
+    /// Calls the EPA was known for.
+    pub plays: usize,
+    pub total: f32,
+}
+
+impl CallValue {
+    /// Mean EPA per call.
+    pub fn mean(&self) -> Option<f32> {
+        match self.plays {
+            0 => None,
+            plays => Some(self.total / plays as f32),
+        }
+    }
+}
+
+impl fmt::Display for CallValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {} plays", self.action, self.plays)?;
+
+        if let Some(mean) = self.mean() {
+            write!(f, ", EPA {:+.2} per play, {:+.2} total", mean, self.total)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Expected points of the next score in the half from each `State`, from the
+/// offence's point of view.
+///
+/// Touchdowns count along with their PAT, and scores by the other team count
+/// against the offence. Halves that end without a score count as 0.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExpectedPoints {
+    /// The state each play was faced in, and the points of the next score.
+    samples: Vec<(State, i8)>,
+}
+
+impl ExpectedPoints {
+    /// Fits the model to every play of `log` whose down faced is known.
+    /// Games flagged `Flags::IgnoreScore` are left out, as their scores are unknown.
+    pub fn fit(log: &LogFile) -> Self {
+        let mut samples = vec![];
+
+        for game in &log.0 {
+            if game.flags.contains(&Flags::IgnoreScore) {
+                continue;
+            }
+
+            for team in game.teams().unwrap_or_default() {
+                samples.extend(
+                    outcomes(game, team)
+                        .into_iter()
+                        .filter_map(|outcome| Some((outcome.before?, outcome.next_score))),
+                )
+            }
+        }
+
+        Self { samples }
+    }
+
+    /// Plays the model was fitted to.
+    pub fn samples(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Expected points from `state`, `None` if nothing was fitted.
+    ///
+    /// States seen on fewer than `MIN_SAMPLES` plays fall back to the same down
+    /// and distance anywhere on the field, then to the same down, then to every play.
+    pub fn value(&self, state: &State) -> Option<f32> {
+        let levels: [&dyn Fn(&State) -> bool; 3] = [
+            &|seen| seen == state,
+            &|seen| seen.down == state.down && seen.distance == state.distance,
+            &|seen| seen.down == state.down,
+        ];
+
+        for matches in levels {
+            let points: Vec<i8> = self
+                .samples
+                .iter()
+                .filter(|(seen, _)| matches(seen))
+                .map(|(_, points)| *points)
+                .collect();
+
+            if points.len() >= MIN_SAMPLES {
+                return mean(&points);
+            }
+        }
+
+        mean(
+            &self
+                .samples
+                .iter()
+                .map(|(_, points)| *points)
+                .collect::<Vec<i8>>(),
+        )
+    }
+
+    /// The mean points from every state seen, most common first.
+    pub fn values(&self) -> Vec<StateValue> {
+        let mut states: Vec<(State, Vec<i8>)> = vec![];
+
+        for (state, points) in &self.samples {
+            match states.iter_mut().find(|(seen, _)| seen == state) {
+                Some((_, seen)) => seen.push(*points),
+                None => states.push((state.to_owned(), vec![*points])),
+            }
+        }
+
+        let mut values: Vec<StateValue> = states
+            .into_iter()
+            .filter_map(|(state, points)| {
+                Some(StateValue {
+                    state,
+                    samples: points.len(),
+                    points: mean(&points)?,
+                })
+            })
+            .collect();
+
+        // Stable, so ties stay in order of first use.
+        values.sort_by_key(|value| Reverse(value.samples));
+
+        values
+    }
+
+    /// Each of `team`'s plays in `game`, in order, with the expected points added.
+    ///
+    /// A play that scores is worth the points of the score, and one followed by a
+    /// change of possession is worth the new offence's 1st & 10 against it.
+    /// Punts and field goal attempts are left to the play before them, through
+    /// the down and distance it left. Nothing is added in games flagged
+    /// `Flags::IgnoreScore`, as their scores are unknown.
+    pub fn play_values(&self, game: &Game, team: Team) -> Vec<PlayValue> {
+        let ignore_score = game.flags.contains(&Flags::IgnoreScore);
+
+        outcomes(game, team)
+            .into_iter()
+            .map(|outcome| {
+                let expected = outcome.before.as_ref().and_then(|state| self.value(state));
+                let after = match outcome.after {
+                    After::Scored(points) => Some(points as f32),
+                    After::Lost(position) => self
+                        .value(&State::first_and_ten(position))
+                        .map(|value| -value),
+                    After::Kept(state) => state.and_then(|state| self.value(&state)),
+                    After::HalfOver => Some(0.0),
+                };
+
+                PlayValue {
+                    play: outcome.play,
+                    before: outcome.before,
+                    expected,
+                    added: after
+                        .zip(expected)
+                        .filter(|_| !ignore_score)
+                        .map(|(after, expected)| after - expected),
+                }
+            })
+            .collect()
+    }
+
+    /// `team`'s EPA calling each known action over `log`, best first.
+    /// Actions never called with a known EPA are left out, as are
+    /// games flagged `Flags::IgnoreActions` or `Flags::IgnoreScore`.
+    pub fn call_values(&self, log: &LogFile, team: Team) -> Vec<CallValue> {
+        let mut calls: Vec<CallValue> = vec![];
+
+        for game in &log.0 {
+            if game.flags.contains(&Flags::IgnoreActions)
+                || !game.teams().unwrap_or_default().contains(&team)
+            {
+                continue;
+            }
+
+            for value in self.play_values(game, team.to_owned()) {
+                let Some(added) = value.added else {
+                    continue;
+                };
+
+                if value.play.action.is_unknown() {
+                    continue;
+                }
+
+                match calls
+                    .iter_mut()
+                    .find(|call| call.action == value.play.action)
+                {
+                    Some(call) => {
+                        call.plays += 1;
+                        call.total += added
+                    }
+                    None => calls.push(CallValue {
+                        action: value.play.action,
+                        plays: 1,
+                        total: added,
+                    }),
+                }
+            }
+        }
+
+        calls.sort_by(|a, b| b.mean().partial_cmp(&a.mean()).unwrap());
+
+        calls
+    }
+}
+
+/// What the offence was left with after a play.
+enum After {
+    /// The points of the score, against the offence for a safety.
+    Scored(i8),
+    /// The ball went to the other team, at this many yards from their own goal line.
+    Lost(Option<u8>),
+    /// The down and distance left, if known.
+    Kept(Option<State>),
+    HalfOver,
+}
+
+struct Outcome {
+    play: Play,
+    before: Option<State>,
+    after: After,
+    /// Points of the next score in the half, from the offence's point of view.
+    next_score: i8,
+}
+
+/// Each of `team`'s plays in `game`, with what it faced and what it led to.
+fn outcomes(game: &Game, team: Team) -> Vec<Outcome> {
+    let scores = game.team_scores();
+    let halves: Vec<usize> = game
+        .events
+        .iter()
+        .enumerate()
+        .filter(|(_, event)| matches!(event, Event::Quarter(Quarter::Third | Quarter::Overtime(_))))
+        .map(|(idx, _)| idx)
+        .collect();
+    let next_score = |from: usize| {
+        let until = halves
+            .iter()
+            .copied()
+            .find(|end| *end > from)
+            .unwrap_or(game.events.len());
+
+        next_score(&scores, from, until, &team)
+    };
+
+    let mut outcomes = vec![];
+
+    for (drive, faced) in game
+        .team_drives(team.to_owned())
+        .into_iter()
+        .zip(game.faced_plays(team.to_owned()))
+    {
+        let positions = drive.field_positions();
+        let plays = drive
+            .events
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, event)| match event {
+                Event::Play(play) => Some((idx, play)),
+                _ => None,
+            });
+
+        // `faced_plays` runs over the same plays, in the same order.
+        for ((idx, play), (faced, _)) in plays.zip(faced) {
+            let at = drive.start + idx;
+            let next = drive.events[idx + 1..]
+                .iter()
+                .chain(game.events.get(drive.end_index()))
+                .find(|event| !matches!(event, Event::Quarter(_)));
+
+            let after = match next {
+                Some(Event::Score(points, _)) if !points.is_pat() => After::Scored(next_score(at)),
+                Some(Event::Turnover(..) | Event::Kickoff(..)) => {
+                    After::Lost(positions[idx].map(|position| 100 - position))
+                }
+                None if matches!(drive.end, DriveEnd::QuarterEnd | DriveEnd::GameEnd) => {
+                    After::HalfOver
+                }
+                _ => After::Kept(State::new(
+                    &DownAndDistance {
+                        down: play.down.to_owned(),
+                        terrain: play.terrain.to_owned(),
+                    },
+                    positions[idx],
+                )),
+            };
+
+            outcomes.push(Outcome {
+                play: play.to_owned(),
+                before: State::new(&faced, idx.checked_sub(1).and_then(|prev| positions[prev])),
+                after,
+                next_score: next_score(at),
+            })
+        }
+    }
+
+    outcomes
+}
+
+/// Points of the first score from event `from` until event `until`, along with
+/// any PAT that followed it, from `team`'s point of view.
+fn next_score(scores: &[TeamScore], from: usize, until: usize, team: &Team) -> i8 {
+    let mut scores = scores
+        .iter()
+        .skip_while(|score| score.event_index < from || score.points.is_pat());
+
+    let Some(first) = scores.next().filter(|score| score.event_index < until) else {
+        return 0;
+    };
+
+    let points = first.points.to_points()
+        + scores
+            .take_while(|score| score.points.is_pat() && score.team == first.team)
+            .map(|score| score.points.to_points())
+            .sum::<u8>();
+
+    if first.team == *team {
+        points as i8
+    } else {
+        -(points as i8)
+    }
+}
+
+fn mean(points: &[i8]) -> Option<f32> {
+    match points.len() {
+        0 => None,
+        n => Some(points.iter().map(|points| *points as f32).sum::<f32>() / n as f32),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{gamelog, play};
+    use crate::{model::expected_points::*, *};
+
+    #[test]
+    fn expected_points() {
+        let mut log = LogFile(vec![Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events: vec![
+                Event::Kickoff(Team::Nebraska, None, None),
+                play(Action::PowerZero, Down::Second, TerrainState::Yards(4)),
+                play(Action::PowerZero, Down::First, TerrainState::Yards(10)),
+                play(Action::PowerZero, None, TerrainState::Yards(0)),
+                Event::Score(ScorePoints::Touchdown, None),
+                Event::Score(ScorePoints::PatFieldGoal, None),
+                Event::Kickoff(Team::Iowa, None, None),
+                play(Action::PowerZero, Down::Second, TerrainState::Yards(10)),
+                play(Action::PowerZero, Down::Third, TerrainState::Yards(10)),
+                Event::Punt {
+                    offence: Team::Nebraska,
+                    distance: None,
+                    return_yards: None,
+                    position: None,
+                },
+                play(Action::PowerZero, Down::Second, TerrainState::Yards(10)),
+                Event::Score(ScorePoints::FieldGoal, None),
+                Event::Quarter(Quarter::Third),
+                Event::Kickoff(Team::Iowa, None, None),
+                play(Action::PowerZero, Down::Second, TerrainState::Yards(8)),
+            ],
+        }]);
+        log.0.push(Game {
+            flags: vec![Flags::IgnoreScore],
+            ..log.0[0].to_owned()
+        });
+
+        let model = ExpectedPoints::fit(&log);
+
+        assert!(model.samples() == 7);
+
+        // Four plays from 1st & 10 led to 7, 7, 3 and 0, and one to Iowa conceding 3.
+        let first_and_ten = State::first_and_ten(None);
+        let value = model.value(&first_and_ten).unwrap();
+
+        assert!((value - 2.8).abs() < 1e-4);
+        assert!(model.values()[0].state == first_and_ten);
+        assert!(model.values()[0].samples == 5);
+
+        // Too few plays on 2nd & 4, or on 2nd down, so every play counts.
+        let second = State {
+            down: Down::Second,
+            distance: Some(DistanceToGo::Medium),
+            zone: None,
+        };
+
+        assert!((model.value(&second).unwrap() - 18.0 / 7.0).abs() < 1e-4);
+
+        let game = &log.0[0];
+        let nebraska = model.play_values(game, Team::Nebraska);
+
+        assert!(nebraska.len() == 4);
+        assert!((nebraska[2].added.unwrap() - 4.2).abs() < 1e-4);
+        assert!(nebraska[2].before == Some(first_and_ten.to_owned()));
+
+        let iowa = model.play_values(game, Team::Iowa);
+
+        // Halves end with nothing.
+        assert!((iowa[2].added.unwrap() + 2.8).abs() < 1e-4);
+
+        // Scores are unknown, so the same plays add nothing.
+        let ignored = model.play_values(&log.0[1], Team::Nebraska);
+
+        assert!(ignored.len() == 4 && ignored.iter().all(|value| value.added.is_none()));
+        assert!(ignored[2].expected == nebraska[2].expected);
+
+        let calls = model.call_values(&log, Team::Nebraska);
+
+        assert!(calls.len() == 1 && calls[0].plays == 4);
+        assert!(ExpectedPoints::default().value(&first_and_ten).is_none());
+    }
+
+    #[test]
+    fn every_play() {
+        let log = gamelog();
+
+        let model = ExpectedPoints::fit(&log);
+        let calls = model.call_values(&log, Team::Nebraska);
+
+        assert!(model.samples() > 0);
+        assert!(
+            model
+                .values()
+                .iter()
+                .map(|value| value.samples)
+                .sum::<usize>()
+                == model.samples()
+        );
+        assert!(!calls.is_empty());
+        assert!(
+            calls
+                .windows(2)
+                .all(|pair| pair[0].mean() >= pair[1].mean())
+        );
+    }
+}
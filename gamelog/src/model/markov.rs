@@ -0,0 +1,391 @@
+use crate::{DistanceToGo, Down, DriveEnd, LogFile, ScorePoints, Team};
+use std::fmt;
+use strum::{EnumIter, IntoEnumIterator};
+
+/// How a drive ended, an absorbing state of `DriveChain`.
+#[derive(Debug, Clone, PartialEq, EnumIter)]
+pub enum DriveOutcome {
+    Touchdown,
+    FieldGoal,
+    /// Scored by the defence.
+    Safety,
+    /// Including missed field goals and turnovers on downs.
+    Turnover,
+    Punt,
+    /// The half, an overtime period or the log ended first.
+    HalfOver,
+}
+
+impl DriveOutcome {
+    /// Every outcome, in order.
+    pub fn all() -> Vec<Self> {
+        Self::iter().collect()
+    }
+
+    pub fn from_end(end: &DriveEnd) -> Self {
+        match end {
+            DriveEnd::Score(ScorePoints::Safety) => Self::Safety,
+            DriveEnd::Score(ScorePoints::FieldGoal) => Self::FieldGoal,
+            DriveEnd::Score(_) => Self::Touchdown,
+            DriveEnd::Turnover(_) => Self::Turnover,
+            DriveEnd::Punt => Self::Punt,
+            DriveEnd::QuarterEnd | DriveEnd::GameEnd => Self::HalfOver,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Touchdown => "TD",
+            Self::FieldGoal => "FG",
+            Self::Safety => "Saf",
+            Self::Turnover => "TO",
+            Self::Punt => "Punt",
+            Self::HalfOver => "Half",
+        }
+    }
+}
+
+/// The down and distance faced before a play, a transient state of `DriveChain`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriveState {
+    pub down: Down,
+    /// `None` where the distance was not known, including at the goal line.
+    pub distance: Option<DistanceToGo>,
+}
+
+impl DriveState {
+    /// Every down and distance, in order.
+    pub fn all() -> Vec<Self> {
+        [Down::First, Down::Second, Down::Third, Down::Fourth]
+            .into_iter()
+            .flat_map(|down| {
+                DistanceToGo::iter()
+                    .map(Some)
+                    .chain([None])
+                    .map(move |distance| Self {
+                        down: down.to_owned(),
+                        distance,
+                    })
+            })
+            .collect()
+    }
+
+    /// e.g. `3&S` for third and short.
+    fn label(&self) -> String {
+        let distance = match &self.distance {
+            Some(DistanceToGo::Short) => "S",
+            Some(DistanceToGo::Medium) => "M",
+            Some(DistanceToGo::Long) => "L",
+            None => "?",
+        };
+
+        format!("{}&{}", self.down.number(), distance)
+    }
+}
+
+impl fmt::Display for DriveState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.distance {
+            Some(distance) => write!(f, "{:?} & {:?}", self.down, distance),
+            None => write!(f, "{:?} & ?", self.down),
+        }
+    }
+}
+
+/// An absorbing Markov chain of drives, moving play by play between each
+/// `DriveState` until they end in a `DriveOutcome`.
+///
+/// Plays with an unknown down faced break the chain, so the transitions into
+/// and out of them are left out. Drives losing track of the down that way leak
+/// out of the chain, leaving some rows of probabilities short of one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriveChain {
+    /// `None` for every team together.
+    pub team: Option<Team>,
+    /// Transient states, as from `DriveState::all`.
+    pub states: Vec<DriveState>,
+    /// Times a drive moved from each of `states` to each of `states`,
+    /// then to each `DriveOutcome`, in order.
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl DriveChain {
+    /// Counts the transitions of `team`'s drives over `log`, or every team's with `None`.
+    pub fn new(log: &LogFile, team: Option<Team>) -> Self {
+        let states = DriveState::all();
+        let width = states.len() + DriveOutcome::iter().count();
+        let mut counts = vec![vec![0; width]; states.len()];
+
+        for game in &log.0 {
+            let teams: Vec<Team> = game
+                .teams()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|present| team.as_ref().is_none_or(|team| team == present))
+                .collect();
+
+            for offence in teams {
+                for (drive, plays) in game
+                    .team_drives(offence.to_owned())
+                    .into_iter()
+                    .zip(game.faced_plays(offence))
+                {
+                    let path: Vec<Option<usize>> = plays
+                        .iter()
+                        .map(|(faced, _)| {
+                            let state = DriveState {
+                                down: faced.down.to_owned()?,
+                                distance: faced.distance(),
+                            };
+
+                            states.iter().position(|known| *known == state)
+                        })
+                        .collect();
+
+                    for pair in path.windows(2) {
+                        if let (Some(from), Some(to)) = (pair[0], pair[1]) {
+                            counts[from][to] += 1
+                        }
+                    }
+
+                    if let Some(Some(last)) = path.last() {
+                        let outcome = DriveOutcome::from_end(&drive.end);
+                        let column = DriveOutcome::iter()
+                            .position(|known| known == outcome)
+                            .unwrap();
+
+                        counts[*last][states.len() + column] += 1
+                    }
+                }
+            }
+        }
+
+        Self {
+            team,
+            states,
+            counts,
+        }
+    }
+
+    /// Transitions made out of each of `states`.
+    pub fn totals(&self) -> Vec<usize> {
+        self.counts.iter().map(|row| row.iter().sum()).collect()
+    }
+
+    /// The probability of moving from each of `states`, to each of `states` then
+    /// each `DriveOutcome`. States never left have a row of zeros.
+    pub fn transition_matrix(&self) -> Vec<Vec<f32>> {
+        self.counts
+            .iter()
+            .zip(self.totals())
+            .map(|(row, total)| {
+                row.iter()
+                    .map(|count| match total {
+                        0 => 0.0,
+                        total => *count as f32 / total as f32,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The probability that a drive from each of `states` ends in each
+    /// `DriveOutcome`, by solving `(I - Q)B = R`.
+    /// Returns `None` if some states can never be left for an outcome.
+    pub fn absorption_probabilities(&self) -> Option<Vec<Vec<f32>>> {
+        let n = self.states.len();
+        let matrix = self.transition_matrix();
+
+        // `I - Q`, augmented with `R`.
+        let mut rows: Vec<Vec<f64>> = matrix
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, p)| {
+                        let identity = if i == j { 1.0 } else { 0.0 };
+
+                        if j < n {
+                            identity - *p as f64
+                        } else {
+                            *p as f64
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        // Gauss-Jordan elimination, with partial pivoting.
+        for column in 0..n {
+            let pivot = (column..n)
+                .max_by(|a, b| rows[*a][column].abs().total_cmp(&rows[*b][column].abs()))?;
+
+            if rows[pivot][column].abs() < 1e-12 {
+                return None;
+            }
+
+            rows.swap(column, pivot);
+
+            let scale = rows[column][column];
+            rows[column].iter_mut().for_each(|value| *value /= scale);
+
+            for row in 0..n {
+                let factor = rows[row][column];
+
+                if row == column || factor == 0.0 {
+                    continue;
+                }
+
+                for k in column..rows[row].len() {
+                    rows[row][k] -= factor * rows[column][k]
+                }
+            }
+        }
+
+        Some(
+            rows.into_iter()
+                .map(|row| row[n..].iter().map(|p| *p as f32).collect())
+                .collect(),
+        )
+    }
+
+    /// The probability that a drive from `state` ends in `outcome`,
+    /// `None` if `state` was never left.
+    pub fn absorption(&self, state: &DriveState, outcome: &DriveOutcome) -> Option<f32> {
+        let row = self.states.iter().position(|known| known == state)?;
+        let column = DriveOutcome::iter().position(|known| known == *outcome)?;
+
+        if self.totals()[row] == 0 {
+            return None;
+        }
+
+        Some(self.absorption_probabilities()?[row][column])
+    }
+}
+
+/// The transition matrix in canonical form, with every absorbing state
+/// staying put, and `-` for states never left.
+impl fmt::Display for DriveChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels: Vec<String> = self
+            .states
+            .iter()
+            .map(DriveState::label)
+            .chain(DriveOutcome::iter().map(|outcome| outcome.label().to_string()))
+            .collect();
+
+        write!(f, "{:>5}", "")?;
+
+        for label in &labels {
+            write!(f, "{:>5}", label)?;
+        }
+
+        let totals = self.totals();
+
+        for (i, row) in self.transition_matrix().iter().enumerate() {
+            write!(f, "\n{:>5}", labels[i])?;
+
+            for p in row {
+                match totals[i] {
+                    0 => write!(f, "{:>5}", "-")?,
+                    _ => write!(f, "{:>5.2}", p)?,
+                }
+            }
+        }
+
+        let n = self.states.len();
+
+        for (i, label) in labels.iter().enumerate().skip(n) {
+            write!(f, "\n{:>5}", label)?;
+
+            for j in 0..labels.len() {
+                write!(f, "{:>5.2}", if i == j { 1.0 } else { 0.0 })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::play;
+    use crate::{model::markov::*, *};
+
+    #[test]
+    fn drive_chain() {
+        let log = LogFile(vec![Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events: vec![
+                Event::Kickoff(Team::Nebraska, None, None),
+                play(Action::Unknown, Down::Second, TerrainState::Yards(4)),
+                play(Action::Unknown, Down::First, TerrainState::Yards(10)),
+                play(Action::Unknown, None, TerrainState::Yards(0)),
+                Event::Score(ScorePoints::Touchdown, None),
+                Event::Score(ScorePoints::PatFieldGoal, None),
+                Event::Kickoff(Team::Iowa, None, None),
+                play(Action::Unknown, Down::Second, TerrainState::Yards(10)),
+                play(Action::Unknown, Down::Third, TerrainState::Yards(10)),
+                Event::Punt {
+                    offence: Team::Nebraska,
+                    distance: None,
+                    return_yards: None,
+                    position: None,
+                },
+                play(Action::Unknown, Down::Second, TerrainState::Yards(10)),
+                Event::Score(ScorePoints::FieldGoal, None),
+            ],
+        }]);
+
+        let first = DriveState {
+            down: Down::First,
+            distance: Some(DistanceToGo::Long),
+        };
+        let second = DriveState {
+            down: Down::Second,
+            distance: Some(DistanceToGo::Medium),
+        };
+
+        let nebraska = DriveChain::new(&log, Some(Team::Nebraska));
+
+        assert!(nebraska.states.len() == 16 && nebraska.counts[0].len() == 22);
+        assert!(nebraska.totals()[2] == 3 && nebraska.totals()[5] == 1);
+        assert!(nebraska.transition_matrix()[5][2] == 1.0);
+
+        // Half of the drives from 1st & 10 score a touchdown, going through 2nd & 4 or not.
+        let touchdown = nebraska
+            .absorption(&first, &DriveOutcome::Touchdown)
+            .unwrap();
+
+        assert!((touchdown - 0.5).abs() < 1e-6);
+        assert!(
+            (nebraska
+                .absorption(&second, &DriveOutcome::FieldGoal)
+                .unwrap()
+                - 0.5)
+                .abs()
+                < 1e-6
+        );
+        assert!(
+            nebraska
+                .absorption(
+                    &DriveState {
+                        down: Down::Fourth,
+                        distance: None,
+                    },
+                    &DriveOutcome::Punt,
+                )
+                .is_none()
+        );
+
+        let league = DriveChain::new(&log, None);
+        let probabilities = league.absorption_probabilities().unwrap();
+
+        assert!((probabilities[2][0] - 1.0 / 3.0).abs() < 1e-6);
+        assert!((probabilities[2][4] - 1.0 / 3.0).abs() < 1e-6);
+        assert!((probabilities[2].iter().sum::<f32>() - 1.0).abs() < 1e-6);
+        assert!(league.to_string().lines().count() == 1 + 16 + 6);
+    }
+}
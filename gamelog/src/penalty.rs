@@ -0,0 +1,124 @@
+use crate::{Down, Team, TerrainState};
+use serde::{Deserialize, Serialize};
+
+/// Details of a penalty beyond its distance, any of which may be left out.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct Penalty {
+    /// The team that committed the foul.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub team: Option<Team>,
+    /// Which side of the ball committed the foul.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<Side>,
+    #[serde(default, skip_serializing_if = "Ruling::is_accepted")]
+    pub ruling: Ruling,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub automatic_first_down: bool,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub enum Side {
+    Offence,
+    Defence,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub enum Ruling {
+    #[default]
+    Accepted,
+    Declined,
+    Offsetting,
+}
+
+impl Ruling {
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, Self::Accepted)
+    }
+}
+
+/// A penalty, attributed to the team that committed it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamPenalty {
+    /// Index into `Game.events` of the `Event::Penalty`.
+    pub event_index: usize,
+    pub team: Team,
+    pub side: Side,
+    /// The down the offence was facing when the flag was thrown.
+    pub down: Down,
+    pub distance: TerrainState,
+    pub ruling: Ruling,
+    pub automatic_first_down: bool,
+}
+
+impl TeamPenalty {
+    /// Yards the penalty was enforced for, `None` if declined, offsetting,
+    /// or not measured in yards.
+    pub fn yards(&self) -> Option<u8> {
+        match (&self.ruling, &self.distance) {
+            (Ruling::Accepted, TerrainState::Yards(yards)) => Some(*yards),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn attribution() {
+        let penalty = |team: Option<Team>, side: Option<Side>, ruling: Ruling| {
+            Event::Penalty(
+                TerrainState::Yards(5),
+                Some(Penalty {
+                    team,
+                    side,
+                    ruling,
+                    automatic_first_down: false,
+                }),
+            )
+        };
+
+        let a = Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events: vec![
+                Event::Kickoff(Team::Nebraska, None, None),
+                Event::Penalty(TerrainState::Yards(10), None),
+                Event::Play(Play {
+                    down: Some(Down::Second),
+                    ..Default::default()
+                }),
+                penalty(None, Some(Side::Defence), Ruling::Accepted),
+                penalty(Some(Team::Nebraska), None, Ruling::Declined),
+                Event::Turnover(Team::Iowa, None),
+                penalty(Some(Team::Nebraska), None, Ruling::Offsetting),
+                Event::Penalty(TerrainState::GoalLine, None),
+            ],
+        };
+
+        let penalties = a.team_penalties();
+
+        assert!(penalties.len() == 5);
+        assert!(penalties[0].team == Team::Nebraska && penalties[0].down == Down::First);
+        assert!(penalties[1].team == Team::Iowa && penalties[1].side == Side::Defence);
+        assert!(penalties[1].down == Down::Second);
+        assert!(penalties[2].side == Side::Offence);
+        assert!(penalties[3].team == Team::Nebraska && penalties[3].side == Side::Defence);
+        assert!(penalties[4].team == Team::Iowa);
+
+        assert!(a.penalties(Team::Nebraska) == 3);
+        assert!(a.penalties(Team::Iowa) == 2);
+        assert!(a.penalty_yards(Team::Nebraska) == 10);
+        assert!(a.penalty_yards(Team::Iowa) == 5);
+        assert!(
+            a.penalties_by_down(Team::Nebraska)
+                == vec![
+                    (Down::First, 2),
+                    (Down::Second, 1),
+                    (Down::Third, 0),
+                    (Down::Fourth, 0),
+                ]
+        );
+    }
+}
@@ -1,7 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
-#[derive(Debug, Deserialize, Clone, PartialEq, EnumIter)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, PartialOrd, EnumIter)]
 pub enum Quarter {
     First,
     Second,
@@ -1,11 +1,14 @@
-use crate::{Action, TerrainState};
-use serde::Deserialize;
+use crate::{Action, DistanceToGo, TerrainState, YardLine};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Play {
     pub action: Action,
     pub down: Option<Down>,
     pub terrain: Option<TerrainState>,
+    /// Where the ball was spotted after the play, if it was noted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<YardLine>,
 }
 
 impl Default for Play {
@@ -14,11 +17,34 @@ impl Default for Play {
             action: Action::default(),
             down: Some(Down::First),
             terrain: Some(TerrainState::Yards(10)),
+            position: None,
         }
     }
 }
 
-#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+/// The down and distance an offence faced before a play, where known.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DownAndDistance {
+    pub down: Option<Down>,
+    pub terrain: Option<TerrainState>,
+}
+
+impl DownAndDistance {
+    /// 1st & 10, as at the start of a drive.
+    pub fn first_and_ten() -> Self {
+        Self {
+            down: Some(Down::First),
+            terrain: Some(TerrainState::Yards(10)),
+        }
+    }
+
+    /// How far there was to go, if known.
+    pub fn distance(&self) -> Option<DistanceToGo> {
+        DistanceToGo::from_terrain(self.terrain.as_ref()?)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
 pub enum Down {
     #[default]
     First,
@@ -26,3 +52,25 @@ pub enum Down {
     Third,
     Fourth,
 }
+
+impl Down {
+    /// Returns 1 for `Down::First` through 4 for `Down::Fourth`.
+    pub fn number(&self) -> u8 {
+        match self {
+            Self::First => 1,
+            Self::Second => 2,
+            Self::Third => 3,
+            Self::Fourth => 4,
+        }
+    }
+
+    /// Returns the down following `self`, `None` after `Down::Fourth`.
+    pub fn next(&self) -> Option<Down> {
+        match self {
+            Self::First => Some(Self::Second),
+            Self::Second => Some(Self::Third),
+            Self::Third => Some(Self::Fourth),
+            Self::Fourth => None,
+        }
+    }
+}
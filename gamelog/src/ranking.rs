@@ -0,0 +1,258 @@
+//! Automatic team rankings, rated game by game over a LogFile.
+
+use crate::{Game, LogFile, Team};
+use std::{cmp::Ordering, fmt};
+
+/// The rating every team starts on.
+pub const INITIAL_RATING: f32 = 1500.0;
+
+/// The most a rating can move in one game, before any margin multiplier.
+pub const K_FACTOR: f32 = 32.0;
+
+/// How a game's result moves the ratings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RatingSystem {
+    /// Plain Elo, counting only wins, losses and ties.
+    Elo,
+    /// Elo, with the K factor scaled up by the margin of victory, and scaled
+    /// back down when the favourite wins, as in FiveThirtyEight's NFL ratings.
+    MarginOfVictory,
+}
+
+impl RatingSystem {
+    /// Scales `K_FACTOR` for a game won by `margin` points, by the team whose
+    /// rating was `difference` above the loser's before it.
+    fn multiplier(&self, margin: u16, difference: f32) -> f32 {
+        match self {
+            Self::Elo => 1.0,
+            Self::MarginOfVictory if margin == 0 => 1.0,
+            Self::MarginOfVictory => (margin as f32 + 1.0).ln() * 2.2 / (difference * 0.001 + 2.2),
+        }
+    }
+}
+
+/// One team's rating, and how it got there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamRating {
+    pub team: Team,
+    pub rating: f32,
+    pub wins: usize,
+    pub losses: usize,
+    pub ties: usize,
+    /// Index into `LogFile.0` of each game rated, and the rating after it.
+    pub history: Vec<(usize, f32)>,
+}
+
+impl TeamRating {
+    fn new(team: Team) -> Self {
+        Self {
+            team,
+            rating: INITIAL_RATING,
+            wins: 0,
+            losses: 0,
+            ties: 0,
+            history: vec![],
+        }
+    }
+
+    pub fn games(&self) -> usize {
+        self.wins + self.losses + self.ties
+    }
+}
+
+/// Every team's rating after each game of a log, in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ranking {
+    pub system: RatingSystem,
+    /// Highest rated first, ties in order of first appearance.
+    pub ratings: Vec<TeamRating>,
+}
+
+impl Ranking {
+    /// Rates every game of `log`, from scratch.
+    /// Rebuild it whenever the log changes, as nothing is carried over.
+    pub fn new(log: &LogFile, system: RatingSystem) -> Self {
+        let mut ranking = Self {
+            system,
+            ratings: vec![],
+        };
+
+        for (idx, game) in log.0.iter().enumerate() {
+            ranking.add_game(idx, game)
+        }
+
+        ranking
+    }
+
+    /// Rates `game`, the `idx`th of its log, after every game before it.
+    ///
+    /// Games flagged `Flags::IgnoreScore`, or without exactly two teams,
+    /// are left out, as they have no result.
+    pub fn add_game(&mut self, idx: usize, game: &Game) {
+        let Some(scoreboard) = game.scoreboard() else {
+            return;
+        };
+
+        let Ok([(a, a_points), (b, b_points)]) =
+            <[(Team, u16); 2]>::try_from(scoreboard.final_score())
+        else {
+            return;
+        };
+
+        let (ra, rb) = (self.rating(&a), self.rating(&b));
+        let expected = 1.0 / (1.0 + 10f32.powf((rb - ra) / 400.0));
+
+        let outcome = a_points.cmp(&b_points);
+        let (result, difference) = match outcome {
+            Ordering::Greater => (1.0, ra - rb),
+            Ordering::Less => (0.0, rb - ra),
+            Ordering::Equal => (0.5, 0.0),
+        };
+        let multiplier = self
+            .system
+            .multiplier(a_points.abs_diff(b_points), difference);
+        let change = K_FACTOR * multiplier * (result - expected);
+
+        for (team, change, outcome) in [(a, change, outcome), (b, -change, outcome.reverse())] {
+            let rating = match self.ratings.iter_mut().find(|rating| rating.team == team) {
+                Some(rating) => rating,
+                None => {
+                    self.ratings.push(TeamRating::new(team));
+                    self.ratings.last_mut().unwrap()
+                }
+            };
+
+            rating.rating += change;
+            rating.history.push((idx, rating.rating));
+
+            match outcome {
+                Ordering::Greater => rating.wins += 1,
+                Ordering::Less => rating.losses += 1,
+                Ordering::Equal => rating.ties += 1,
+            }
+        }
+
+        // Stable, so ties stay in order of first appearance.
+        self.ratings
+            .sort_by(|a, b| b.rating.partial_cmp(&a.rating).unwrap());
+    }
+
+    /// `team`'s current rating, `INITIAL_RATING` if it has not played.
+    pub fn rating(&self, team: &Team) -> f32 {
+        self.ratings
+            .iter()
+            .find(|rating| rating.team == *team)
+            .map_or(INITIAL_RATING, |rating| rating.rating)
+    }
+}
+
+/// A ranked table, with each team's rating after every game it played.
+impl fmt::Display for Ranking {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (rank, rating) in self.ratings.iter().enumerate() {
+            let history: Vec<String> = [INITIAL_RATING]
+                .into_iter()
+                .chain(rating.history.iter().map(|(_, rating)| *rating))
+                .map(|rating| format!("{:.0}", rating))
+                .collect();
+
+            if rank > 0 {
+                writeln!(f)?;
+            }
+
+            write!(
+                f,
+                "#{} {:?}: {:.1} ({}-{}-{}), {}",
+                rank + 1,
+                rating.team,
+                rating.rating,
+                rating.wins,
+                rating.losses,
+                rating.ties,
+                history.join(" > ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::gamelog;
+    use crate::{ranking::*, *};
+
+    /// `a` receives the opening kickoff and scores `touchdowns`, then `b` does.
+    fn game(a: Team, a_touchdowns: usize, b: Team, b_touchdowns: usize) -> Game {
+        let mut events = vec![Event::Kickoff(a, None, None)];
+
+        events.extend((0..a_touchdowns).map(|_| Event::Score(ScorePoints::Touchdown, None)));
+        events.push(Event::Kickoff(b, None, None));
+        events.extend((0..b_touchdowns).map(|_| Event::Score(ScorePoints::Touchdown, None)));
+
+        Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events,
+        }
+    }
+
+    #[test]
+    fn elo() {
+        let log = LogFile(vec![
+            game(Team::Nebraska, 2, Team::Iowa, 0),
+            game(Team::Iowa, 1, Team::Colorado, 1),
+            Game {
+                version: crate::MIN_VER,
+                flags: vec![Flags::IgnoreScore],
+                events: game(Team::Colorado, 5, Team::Nebraska, 0).events,
+            },
+        ]);
+
+        let ranking = Ranking::new(&log, RatingSystem::Elo);
+
+        assert!(ranking.ratings.len() == 3);
+        assert!(ranking.ratings[0].team == Team::Nebraska);
+        assert!(ranking.ratings[0].rating == 1516.0);
+        assert!(ranking.ratings[0].history == vec![(0, 1516.0)]);
+        assert!(ranking.ratings[0].wins == 1 && ranking.ratings[0].games() == 1);
+
+        // Colorado drew with a weaker team, and lost rating for it.
+        assert!(ranking.ratings[1].team == Team::Colorado);
+        assert!(ranking.ratings[1].ties == 1);
+        assert!(ranking.rating(&Team::Colorado) < INITIAL_RATING);
+        assert!(ranking.rating(&Team::Iowa) > 1484.0);
+        assert!(ranking.rating(&Team::TexasAnM) == INITIAL_RATING);
+
+        // Elo is zero-sum.
+        let total: f32 = ranking.ratings.iter().map(|rating| rating.rating).sum();
+
+        assert!((total - 3.0 * INITIAL_RATING).abs() < 1e-3);
+        assert!(ranking.to_string().lines().count() == 3);
+
+        let margin = Ranking::new(&log, RatingSystem::MarginOfVictory);
+
+        // Won by 12, between even teams.
+        assert!((margin.rating(&Team::Nebraska) - (1500.0 + 16.0 * 13f32.ln())).abs() < 1e-3);
+    }
+
+    #[test]
+    fn every_game() {
+        let log = gamelog();
+
+        let ranking = Ranking::new(&log, RatingSystem::MarginOfVictory);
+        let rated = log
+            .0
+            .iter()
+            .filter(|game| game.scoreboard().is_some())
+            .count();
+
+        assert!(ranking.ratings.iter().map(TeamRating::games).sum::<usize>() == 2 * rated);
+        assert!(
+            ranking
+                .ratings
+                .windows(2)
+                .all(|pair| pair[0].rating >= pair[1].rating)
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{Quarter, ScorePoints, Team};
+
+/// A score, attributed to the team it counts for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamScore {
+    /// Index into `Game.events` of the `Event::Score`.
+    pub event_index: usize,
+    pub quarter: Quarter,
+    pub team: Team,
+    pub points: ScorePoints,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scoreboard {
+    pub scores: Vec<TeamScore>,
+    /// Each team's cumulative points after every event, indexed like `Game.events`.
+    pub running: Vec<Vec<(Team, u16)>>,
+}
+
+impl Scoreboard {
+    /// Builds the running score over `len` events from attributed `scores`.
+    pub fn new(teams: &[Team], scores: Vec<TeamScore>, len: usize) -> Self {
+        let mut totals: Vec<(Team, u16)> = teams.iter().map(|team| (team.to_owned(), 0)).collect();
+        let mut running = vec![];
+
+        for idx in 0..len {
+            scores
+                .iter()
+                .filter(|score| score.event_index == idx)
+                .for_each(|score| {
+                    if let Some(total) = totals.iter_mut().find(|(team, _)| *team == score.team) {
+                        total.1 += u16::from(score.points.to_points())
+                    }
+                });
+
+            running.push(totals.to_owned())
+        }
+
+        Self { scores, running }
+    }
+
+    /// Each team's points at the end of the game.
+    pub fn final_score(&self) -> Vec<(Team, u16)> {
+        self.running.last().cloned().unwrap_or_default()
+    }
+
+    pub fn points(&self, team: &Team) -> u16 {
+        self.scores
+            .iter()
+            .filter(|score| score.team == *team)
+            .map(|score| u16::from(score.points.to_points()))
+            .sum()
+    }
+
+    pub fn points_in(&self, team: &Team, quarter: &Quarter) -> u16 {
+        self.scores
+            .iter()
+            .filter(|score| score.team == *team && score.quarter == *quarter)
+            .map(|score| u16::from(score.points.to_points()))
+            .sum()
+    }
+
+    /// Returns the team with the most points, `None` if tied.
+    pub fn winner(&self) -> Option<Team> {
+        let mut final_score = self.final_score();
+        final_score.sort_by_key(|(_, points)| std::cmp::Reverse(*points));
+
+        match final_score.as_slice() {
+            [(team, _)] => Some(team.to_owned()),
+            [(team, first), (_, second), ..] if first > second => Some(team.to_owned()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn game(flags: Vec<Flags>, events: Vec<Event>) -> Game {
+        Game {
+            version: crate::MIN_VER,
+            flags,
+            events,
+        }
+    }
+
+    #[test]
+    fn attribution() {
+        let a = game(
+            vec![],
+            vec![
+                Event::Quarter(Quarter::First),
+                Event::Kickoff(Team::Nebraska, None, None),
+                Event::Play(Play::default()),
+                Event::Score(ScorePoints::Touchdown, None),
+                Event::Score(ScorePoints::PatFieldGoal, None),
+                Event::Kickoff(Team::Iowa, None, None),
+                Event::Quarter(Quarter::Second),
+                Event::Play(Play::default()),
+                Event::Score(ScorePoints::Safety, None),
+                Event::Kickoff(Team::Nebraska, None, None),
+                Event::Score(ScorePoints::FieldGoal, Some(Team::Iowa)),
+            ],
+        );
+
+        let scoreboard = a.scoreboard().unwrap();
+
+        assert!(
+            scoreboard
+                .scores
+                .iter()
+                .map(|score| score.team.to_owned())
+                .collect::<Vec<Team>>()
+                == vec![Team::Nebraska, Team::Nebraska, Team::Nebraska, Team::Iowa]
+        );
+        assert!(scoreboard.running.len() == a.events.len());
+        assert!(scoreboard.running[3] == vec![(Team::Nebraska, 6), (Team::Iowa, 0)]);
+        assert!(scoreboard.final_score() == vec![(Team::Nebraska, 9), (Team::Iowa, 3)]);
+        assert!(scoreboard.points_in(&Team::Nebraska, &Quarter::Second) == 2);
+        assert!(scoreboard.winner() == Some(Team::Nebraska));
+    }
+
+    #[test]
+    fn ignore_score() {
+        let a = game(
+            vec![Flags::IgnoreScore],
+            vec![
+                Event::Kickoff(Team::Nebraska, None, None),
+                Event::Score(ScorePoints::Touchdown, None),
+            ],
+        );
+
+        assert!(a.scoreboard().is_none());
+    }
+
+    #[test]
+    fn tie() {
+        let a = game(
+            vec![],
+            vec![
+                Event::Kickoff(Team::Nebraska, None, None),
+                Event::Score(ScorePoints::FieldGoal, None),
+                Event::Kickoff(Team::Iowa, None, None),
+                Event::Score(ScorePoints::FieldGoal, None),
+            ],
+        );
+
+        assert!(a.scoreboard().unwrap().winner().is_none());
+    }
+}
@@ -0,0 +1,527 @@
+//! Maps games and events back to where they were written in a logfile,
+//! and renders diagnostics against that text.
+
+use crate::{error, validate};
+use std::{fmt::Write, path::PathBuf};
+
+/// A 1-based line and column, in characters.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// From `start` up to, but excluding, `end`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// A logfile's text, along with the span of each game and event within it.
+#[derive(Debug, Clone)]
+pub struct Source {
+    pub name: String,
+    pub text: String,
+    games: Vec<Span>,
+    events: Vec<Vec<Span>>,
+}
+
+impl Source {
+    pub fn new(name: String, text: String) -> Self {
+        let (games, events) = locate(&text);
+
+        Self {
+            name,
+            text,
+            games,
+            events,
+        }
+    }
+
+    pub fn read(path: PathBuf) -> Result<Self, error::LogFileError> {
+        match std::fs::read_to_string(path.as_path()) {
+            Ok(text) => Ok(Self::new(path.display().to_string(), text)),
+            Err(err) => Err(error::LogFileError::FailedToOpen(err)),
+        }
+    }
+
+    /// Returns where the game at `game_index` was written.
+    pub fn game_span(&self, game_index: usize) -> Option<Span> {
+        self.games.get(game_index).copied()
+    }
+
+    /// Returns where the event at `event_index` of the game at `game_index` was written.
+    pub fn event_span(&self, game_index: usize, event_index: usize) -> Option<Span> {
+        self.events.get(game_index)?.get(event_index).copied()
+    }
+
+    /// Renders `message` rustc-style, quoting the first line of `span`
+    /// and underlining it with `label`.
+    pub fn render(
+        &self,
+        severity: validate::Severity,
+        message: &str,
+        span: Span,
+        label: &str,
+    ) -> String {
+        let line = self.text.lines().nth(span.start.line - 1).unwrap_or("");
+        let gutter = " ".repeat(span.start.line.to_string().len());
+
+        // Underline to the end of the span, or the end of the line if the span continues.
+        let end = if span.end.line == span.start.line {
+            span.end.column
+        } else {
+            line.trim_end().chars().count() + 1
+        };
+
+        let mut out = String::new();
+
+        // Writing to a String cannot fail.
+        let _ = writeln!(out, "{}: {}", severity, message);
+        let _ = writeln!(
+            out,
+            "{}--> {}:{}:{}",
+            gutter, self.name, span.start.line, span.start.column
+        );
+        let _ = writeln!(out, "{} |", gutter);
+        let _ = writeln!(out, "{} | {}", span.start.line, line);
+        let _ = write!(
+            out,
+            "{} | {}{} {}",
+            gutter,
+            " ".repeat(span.start.column - 1),
+            "^".repeat(end.saturating_sub(span.start.column).max(1)),
+            label
+        );
+
+        out
+    }
+
+    /// Renders `diagnostic` against the event, or game, it concerns.
+    pub fn render_diagnostic(&self, diagnostic: &validate::Diagnostic) -> String {
+        let span = match diagnostic.event_index {
+            Some(event) => self.event_span(diagnostic.game_index, event),
+            None => self.game_span(diagnostic.game_index),
+        };
+
+        let label = match diagnostic.event_index {
+            Some(event) => format!("game {}, event {}", diagnostic.game_index, event),
+            None => format!("game {}", diagnostic.game_index),
+        };
+
+        match span {
+            Some(span) => self.render(diagnostic.severity, &diagnostic.message, span, &label),
+            None => diagnostic.to_string(),
+        }
+    }
+
+    /// Renders a parse error against where it occured.
+    pub fn render_spanned(&self, err: &ron::error::SpannedError) -> String {
+        let start = Position {
+            line: err.position.line,
+            column: err.position.col,
+        };
+
+        self.render(
+            validate::Severity::Error,
+            &err.code.to_string(),
+            Span { start, end: start },
+            "here",
+        )
+    }
+
+    /// Renders `err` against where it occured, if it has a location.
+    pub fn render_error(&self, err: &error::LogFileError) -> String {
+        let severity = validate::Severity::Error;
+
+        match err {
+            error::LogFileError::RonSpannedError(err) => self.render_spanned(err),
+            error::LogFileError::IncompatibleVersion { game_index, .. } => {
+                match self.game_span(*game_index) {
+                    Some(span) => self.render(
+                        severity,
+                        &err.to_string(),
+                        span,
+                        &format!("game {}", game_index),
+                    ),
+                    None => format!("{}: {}", severity, err),
+                }
+            }
+            _ => format!("{}: {}", severity, err),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// One of `[](){},:`
+    Punct(char),
+    /// Identifiers, numbers, and anything else between punctuation.
+    Word(String),
+    Str,
+}
+
+/// Splits `text` into tokens, skipping whitespace and comments.
+fn tokenize(text: &str) -> Vec<(Token, Span)> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = vec![];
+    let mut idx = 0;
+    let mut pos = Position { line: 1, column: 1 };
+
+    // Moves over `chars[idx]`, keeping `pos` in step.
+    let advance = |idx: &mut usize, pos: &mut Position| {
+        if chars[*idx] == '\n' {
+            pos.line += 1;
+            pos.column = 1;
+        } else {
+            pos.column += 1;
+        }
+
+        *idx += 1;
+    };
+
+    while idx < chars.len() {
+        let start = pos;
+        let c = chars[idx];
+
+        if c.is_whitespace() {
+            advance(&mut idx, &mut pos);
+        } else if c == '/' && chars.get(idx + 1) == Some(&'/') {
+            while idx < chars.len() && chars[idx] != '\n' {
+                advance(&mut idx, &mut pos);
+            }
+        } else if c == '/' && chars.get(idx + 1) == Some(&'*') {
+            let mut depth = 0;
+
+            while idx < chars.len() {
+                if chars[idx] == '/' && chars.get(idx + 1) == Some(&'*') {
+                    depth += 1;
+                    advance(&mut idx, &mut pos);
+                } else if chars[idx] == '*' && chars.get(idx + 1) == Some(&'/') {
+                    depth -= 1;
+                    advance(&mut idx, &mut pos);
+                }
+
+                advance(&mut idx, &mut pos);
+
+                if depth == 0 {
+                    break;
+                }
+            }
+        } else if c == '"' {
+            advance(&mut idx, &mut pos);
+
+            while idx < chars.len() && chars[idx] != '"' {
+                if chars[idx] == '\\' {
+                    advance(&mut idx, &mut pos);
+                }
+
+                if idx < chars.len() {
+                    advance(&mut idx, &mut pos);
+                }
+            }
+
+            if idx < chars.len() {
+                advance(&mut idx, &mut pos);
+            }
+
+            tokens.push((Token::Str, Span { start, end: pos }));
+        } else if "[](){},:".contains(c) {
+            advance(&mut idx, &mut pos);
+            tokens.push((Token::Punct(c), Span { start, end: pos }));
+        } else {
+            let mut word = String::new();
+
+            while idx < chars.len()
+                && !chars[idx].is_whitespace()
+                && !"[](){},:\"".contains(chars[idx])
+                && (chars[idx] != '/' || !matches!(chars.get(idx + 1), Some('/' | '*')))
+            {
+                word.push(chars[idx]);
+                advance(&mut idx, &mut pos);
+            }
+
+            tokens.push((Token::Word(word), Span { start, end: pos }));
+        }
+    }
+
+    tokens
+}
+
+/// Just enough of a RON value's structure to find games and events in.
+#[derive(Debug)]
+enum Node {
+    List(Vec<(Span, Node)>),
+    Tuple(Vec<(Span, Node)>),
+    Struct(Vec<(String, Node)>),
+    Atom,
+}
+
+struct Parser {
+    tokens: Vec<(Token, Span)>,
+    idx: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.idx).map(|(token, _)| token)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.idx + offset).map(|(token, _)| token)
+    }
+
+    fn eat(&mut self, punct: char) -> bool {
+        if self.peek() == Some(&Token::Punct(punct)) {
+            self.idx += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Skips `#![...]` attributes.
+    fn skip_attributes(&mut self) {
+        while let Some(Token::Word(word)) = self.peek()
+            && word.starts_with('#')
+        {
+            self.idx += 1;
+
+            let mut depth = 0;
+
+            while let Some(token) = self.peek().cloned() {
+                self.idx += 1;
+
+                match token {
+                    Token::Punct('[' | '(') => depth += 1,
+                    Token::Punct(']' | ')') => {
+                        depth -= 1;
+
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    /// Parses one value, returning it along with the span it covers.
+    fn value(&mut self) -> Option<(Span, Node)> {
+        let start = self.tokens.get(self.idx)?.1.start;
+
+        let node = match self.peek()? {
+            Token::Punct('[') => {
+                self.idx += 1;
+                Node::List(self.items(']'))
+            }
+            Token::Punct('(') => self.parens(),
+            Token::Punct('{') => {
+                self.idx += 1;
+                self.items('}');
+                Node::Atom
+            }
+            Token::Word(_) if self.peek_at(1) == Some(&Token::Punct('(')) => {
+                self.idx += 1;
+                self.parens()
+            }
+            Token::Punct(_) => return None,
+            _ => {
+                self.idx += 1;
+                Node::Atom
+            }
+        };
+
+        let end = self.tokens[self.idx - 1].1.end;
+
+        Some((Span { start, end }, node))
+    }
+
+    /// Parses a parenthesised struct or tuple, starting at the `(`.
+    fn parens(&mut self) -> Node {
+        self.idx += 1;
+
+        let is_struct = matches!(self.peek(), Some(Token::Word(_)))
+            && self.peek_at(1) == Some(&Token::Punct(':'));
+
+        if !is_struct {
+            return Node::Tuple(self.items(')'));
+        }
+
+        let mut fields = vec![];
+
+        while let Some(Token::Word(name)) = self.peek() {
+            let name = name.to_owned();
+            self.idx += 1;
+
+            if !self.eat(':') {
+                break;
+            }
+
+            match self.value() {
+                Some((_, node)) => fields.push((name, node)),
+                None => break,
+            }
+
+            if !self.eat(',') {
+                break;
+            }
+        }
+
+        self.eat(')');
+
+        Node::Struct(fields)
+    }
+
+    /// Parses comma separated values up to and including `close`.
+    /// Map entries are parsed as two values and should be ignored.
+    fn items(&mut self, close: char) -> Vec<(Span, Node)> {
+        let mut items = vec![];
+
+        while self.peek().is_some() && !self.eat(close) {
+            match self.value() {
+                Some(item) => items.push(item),
+                None => {
+                    // Skip whatever cannot start a value, such as a map's `:`.
+                    self.idx += 1;
+                    continue;
+                }
+            }
+
+            self.eat(',');
+        }
+
+        items
+    }
+}
+
+/// Finds the span of every game, and every event within each game.
+/// Anything that cannot be located is left out.
+fn locate(text: &str) -> (Vec<Span>, Vec<Vec<Span>>) {
+    let mut parser = Parser {
+        tokens: tokenize(text),
+        idx: 0,
+    };
+
+    parser.skip_attributes();
+
+    let games = match parser.value() {
+        Some((_, Node::List(games))) => games,
+        // Without `unwrap_newtypes`, the list is wrapped as `LogFile([...])`.
+        Some((_, Node::Tuple(mut items))) if items.len() == 1 => match items.remove(0).1 {
+            Node::List(games) => games,
+            _ => return (vec![], vec![]),
+        },
+        _ => return (vec![], vec![]),
+    };
+
+    let events = games
+        .iter()
+        .map(|(_, game)| match game {
+            Node::Struct(fields) => fields
+                .iter()
+                .find_map(|(name, node)| match node {
+                    Node::List(events) if name == "events" => {
+                        Some(events.iter().map(|(span, _)| *span).collect())
+                    }
+                    _ => None,
+                })
+                .unwrap_or_default(),
+            _ => vec![],
+        })
+        .collect();
+
+    (games.iter().map(|(span, _)| *span).collect(), events)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::source::*;
+    use crate::testing::gamelog_path;
+    use crate::*;
+
+    fn gamelog() -> Source {
+        Source::read(gamelog_path()).unwrap()
+    }
+
+    #[test]
+    fn locate() {
+        let source = gamelog();
+        let log = LogFile::parse(&source.text, VersionPolicy::Strict)
+            .unwrap()
+            .0;
+
+        // Every game and event is found.
+        for (game_index, game) in log.0.iter().enumerate() {
+            assert!(source.game_span(game_index).is_some());
+            assert!(
+                source
+                    .event_span(game_index, game.events.len() - 1)
+                    .is_some()
+            );
+            assert!(source.event_span(game_index, game.events.len()).is_none());
+        }
+
+        assert!(source.game_span(0).unwrap().start == Position { line: 5, column: 5 });
+        assert!(
+            source.event_span(0, 1).unwrap()
+                == Span {
+                    start: Position {
+                        line: 13,
+                        column: 13
+                    },
+                    end: Position {
+                        line: 13,
+                        column: 34
+                    },
+                }
+        );
+        assert!(
+            source.event_span(0, 2).unwrap().end
+                == Position {
+                    line: 18,
+                    column: 14
+                }
+        );
+    }
+
+    #[test]
+    fn comments_and_strings() {
+        let source = Source::new(
+            "test".to_string(),
+            r#"[
+                /* Game( */ Game(
+                    version: "0.8.0", // events: [
+                    flags: [],
+                    events: [Quarter(First), Penalty(Yards(15))],
+                ),
+            ]"#
+            .to_string(),
+        );
+
+        assert!(source.event_span(0, 1).unwrap().start.column == 46);
+        assert!(source.event_span(0, 2).is_none());
+    }
+
+    #[test]
+    fn render() {
+        let source = gamelog();
+
+        let diagnostic = validate::Diagnostic {
+            severity: validate::Severity::Error,
+            game_index: 0,
+            event_index: Some(1),
+            message: "Something is wrong".to_string(),
+        };
+
+        let rendered = source.render_diagnostic(&diagnostic);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert!(lines[0] == "error: Something is wrong");
+        assert!(lines[1].ends_with("gamelog.ron:13:13"));
+        assert!(lines[3] == "13 |             Kickoff(ArizonaState),");
+        assert!(lines[4] == "   |             ^^^^^^^^^^^^^^^^^^^^^ game 0, event 1");
+    }
+}
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub enum FieldGoalResult {
+    Good,
+    Missed,
+    Blocked,
+}
+
+/// Field goal distances, grouped the way kickers are usually judged.
+#[derive(Debug, Clone, PartialEq, EnumIter)]
+pub enum DistanceBand {
+    Under30,
+    Thirties,
+    Forties,
+    FiftyPlus,
+}
+
+impl DistanceBand {
+    pub fn from_distance(distance: u8) -> Self {
+        match distance {
+            0..30 => Self::Under30,
+            30..40 => Self::Thirties,
+            40..50 => Self::Forties,
+            _ => Self::FiftyPlus,
+        }
+    }
+}
+
+/// A team's field goal attempts from one `DistanceBand`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldGoalBand {
+    pub band: DistanceBand,
+    pub made: usize,
+    pub attempts: usize,
+}
+
+impl FieldGoalBand {
+    /// The fraction of attempts made, `None` if there were none.
+    pub fn percentage(&self) -> Option<f32> {
+        if self.attempts == 0 {
+            return None;
+        }
+
+        Some(self.made as f32 / self.attempts as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn special_teams() {
+        let attempt =
+            |distance: u8, result: FieldGoalResult| Event::FieldGoalAttempt { distance, result };
+
+        let a = Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events: vec![
+                Event::Kickoff(Team::Nebraska, Some(YardLine::Own(25)), Some(20)),
+                Event::Play(Play::default()),
+                Event::Punt {
+                    offence: Team::Iowa,
+                    distance: Some(45),
+                    return_yards: Some(5),
+                    position: Some(YardLine::Own(30)),
+                },
+                attempt(52, FieldGoalResult::Missed),
+                Event::Turnover(Team::Nebraska, Some(TurnoverKind::MissedFieldGoal)),
+                Event::Play(Play::default()),
+                Event::Punt {
+                    offence: Team::Iowa,
+                    distance: Some(38),
+                    return_yards: None,
+                    position: None,
+                },
+                Event::Punt {
+                    offence: Team::Nebraska,
+                    distance: None,
+                    return_yards: None,
+                    position: None,
+                },
+                attempt(24, FieldGoalResult::Good),
+                Event::Score(ScorePoints::FieldGoal, None),
+                attempt(35, FieldGoalResult::Blocked),
+            ],
+        };
+
+        let drives = a.drives();
+
+        assert!(drives[0].end == DriveEnd::Punt);
+        assert!(drives[1].start_position() == Some(30));
+        assert!(drives[1].end == DriveEnd::Turnover(Some(TurnoverKind::MissedFieldGoal)));
+        assert!(drives[4].end == DriveEnd::Score(ScorePoints::FieldGoal));
+
+        assert!(a.net_punt_average(Team::Nebraska) == Some(39.0));
+        assert!(a.net_punt_average(Team::Iowa).is_none());
+
+        let bands = a.field_goals_by_distance(Team::Nebraska);
+
+        assert!(bands[0].made == 1 && bands[0].attempts == 1);
+        assert!(bands[1].attempts == 1 && bands[1].percentage() == Some(0.0));
+        assert!(bands[2].percentage().is_none());
+        assert!(a.field_goals_by_distance(Team::Iowa)[3].attempts == 1);
+    }
+}
@@ -0,0 +1,510 @@
+//! Per-team statistics gathered over every game of a LogFile.
+
+use crate::{
+    Action, DistanceBand, Down, FieldGoalBand, Flags, Game, Key, LogFile, Playset, Quarter, Team,
+    TurnoverKind,
+};
+use strum::IntoEnumIterator;
+
+mod bias;
+mod compare;
+mod conversion;
+mod efficiency;
+mod hypothesis;
+mod momentum;
+mod patterns;
+mod plays;
+mod splits;
+mod summary;
+
+pub use bias::*;
+pub use compare::*;
+pub use conversion::*;
+pub use efficiency::*;
+pub use hypothesis::*;
+pub use momentum::*;
+pub use patterns::*;
+pub use plays::*;
+pub use splits::*;
+pub use summary::*;
+
+const DOWNS: [Down; 4] = [Down::First, Down::Second, Down::Third, Down::Fourth];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamStats {
+    pub team: Team,
+    // Terrain
+    /// Every delta over every game, in order.
+    pub deltas: Vec<i8>,
+    /// `None` for games without any gains.
+    pub avg_terrain_gain: Vec<Option<f32>>,
+    /// `None` for games without any losses.
+    pub avg_terrain_loss: Vec<Option<f32>>,
+    /// `None` for games without any deltas.
+    pub avg_terrain_delta: Vec<Option<f32>>,
+    // Play rate
+    /// Plays in every regulation quarter of every game, in order.
+    pub quarterly_plays: Vec<usize>,
+    pub plays_per_quarter: Vec<Option<f32>>,
+    pub plays_per_game: Vec<usize>,
+    // Penalties
+    pub penalties_per_game: Vec<usize>,
+    pub penalty_yards_per_game: Vec<u16>,
+    /// Totals over every game, by the down faced.
+    pub penalties_by_down: Vec<(Down, usize)>,
+    // Turnovers
+    pub turnover_margin_per_game: Vec<i16>,
+    /// Totals over every game, only where the kind was noted.
+    pub turnovers_by_kind: Vec<(TurnoverKind, usize)>,
+    // Special teams
+    /// Only for games where punt distances were noted.
+    pub net_punt_average: Vec<f32>,
+    /// Totals over every game.
+    pub field_goals_by_distance: Vec<FieldGoalBand>,
+    // Field position
+    /// Yards from own goal line, only for games where it was noted.
+    pub avg_start_position: Vec<f32>,
+    pub red_zone_trips_per_game: Vec<usize>,
+    // Score
+    /// Four per game, First through Fourth, for games where scores are kept.
+    pub points_per_quarter: Vec<u16>,
+    pub points_per_game: Vec<u16>,
+    // Biases
+    /// Times each known action was called, in order of first use.
+    /// Games flagged `Flags::IgnoreActions` are left out.
+    pub play_counts: Vec<(Action, usize)>,
+    /// Times each key was used, in order of first use.
+    pub key_counts: Vec<(Key, usize)>,
+    /// Times each playset was used, in order of first use.
+    pub playset_counts: Vec<(Playset, usize)>,
+    pub most_common_play: Option<Action>,
+    pub least_common_play: Option<Action>,
+    pub most_common_key: Option<Key>,
+    pub least_common_key: Option<Key>,
+    pub most_common_playset: Option<Playset>,
+    pub least_common_playset: Option<Playset>,
+    // Traits
+    /// Totals over every game, by the down a first down was gained on.
+    pub first_downs_by_down: Vec<(Down, usize)>,
+    // Conversions
+    /// Third and fourth down totals over every game, by distance.
+    pub conversions: Vec<ConversionCount>,
+    /// `None` for games without any third down plays.
+    pub third_down_rate_per_game: Vec<Option<f32>>,
+    /// `None` for games without any fourth down plays.
+    pub fourth_down_rate_per_game: Vec<Option<f32>>,
+    /// Typical number of downs to achieve 10 yards:
+    /// the median down that first downs were gained on.
+    pub time_to_first_down: Option<Down>,
+}
+
+impl TeamStats {
+    pub fn new(team: Team) -> Self {
+        TeamStats {
+            team,
+            deltas: vec![],
+            avg_terrain_gain: vec![],
+            avg_terrain_loss: vec![],
+            avg_terrain_delta: vec![],
+            quarterly_plays: vec![],
+            plays_per_quarter: vec![],
+            plays_per_game: vec![],
+            penalties_per_game: vec![],
+            penalty_yards_per_game: vec![],
+            penalties_by_down: DOWNS.into_iter().map(|down| (down, 0)).collect(),
+            turnover_margin_per_game: vec![],
+            turnovers_by_kind: TurnoverKind::iter().map(|kind| (kind, 0)).collect(),
+            net_punt_average: vec![],
+            field_goals_by_distance: DistanceBand::iter()
+                .map(|band| FieldGoalBand {
+                    band,
+                    made: 0,
+                    attempts: 0,
+                })
+                .collect(),
+            avg_start_position: vec![],
+            red_zone_trips_per_game: vec![],
+            points_per_quarter: vec![],
+            points_per_game: vec![],
+            play_counts: vec![],
+            key_counts: vec![],
+            playset_counts: vec![],
+            most_common_play: None,
+            least_common_play: None,
+            most_common_key: None,
+            least_common_key: None,
+            most_common_playset: None,
+            least_common_playset: None,
+            first_downs_by_down: DOWNS.into_iter().map(|down| (down, 0)).collect(),
+            time_to_first_down: None,
+            conversions: ConversionCount::empty(),
+            third_down_rate_per_game: vec![],
+            fourth_down_rate_per_game: vec![],
+        }
+    }
+
+    /// Builds `team`'s stats from every game of `log` it took part in.
+    pub fn from_log(log: &LogFile, team: Team) -> Self {
+        let mut stats = Self::new(team);
+
+        log.0.iter().for_each(|game| stats.add_game(game));
+
+        stats
+    }
+
+    /// Adds `game` to the stats, if the team played in it and is not ignored.
+    pub fn add_game(&mut self, game: &Game) {
+        let team = self.team.to_owned();
+
+        match game.teams() {
+            Ok(teams) if teams.contains(&team) => (),
+            _ => return,
+        }
+
+        if game.flags.contains(&Flags::IgnoreTeam(team.to_owned())) {
+            return;
+        }
+
+        self.deltas.extend(game.deltas(team.to_owned()));
+        self.avg_terrain_gain.push(game.avg_gain(team.to_owned()));
+        self.avg_terrain_loss.push(game.avg_loss(team.to_owned()));
+        self.avg_terrain_delta.push(game.avg_delta(team.to_owned()));
+
+        self.quarterly_plays
+            .extend(game.quarterly_plays(team.to_owned()));
+        self.plays_per_quarter
+            .push(game.avg_plays_per_quarter(team.to_owned()));
+        self.plays_per_game.push(game.team_plays(team.to_owned()));
+
+        self.penalties_per_game
+            .push(game.penalties(team.to_owned()));
+        self.penalty_yards_per_game
+            .push(game.penalty_yards(team.to_owned()));
+        add_counts(
+            &mut self.penalties_by_down,
+            game.penalties_by_down(team.to_owned()),
+        );
+
+        self.turnover_margin_per_game
+            .push(game.turnover_margin(team.to_owned()));
+        add_counts(
+            &mut self.turnovers_by_kind,
+            game.turnovers_by_kind(team.to_owned()),
+        );
+
+        if let Some(net) = game.net_punt_average(team.to_owned()) {
+            self.net_punt_average.push(net);
+        }
+
+        for band in game.field_goals_by_distance(team.to_owned()) {
+            if let Some(total) = self
+                .field_goals_by_distance
+                .iter_mut()
+                .find(|total| total.band == band.band)
+            {
+                total.made += band.made;
+                total.attempts += band.attempts;
+            }
+        }
+
+        if let Some(start) = game.avg_start_position(team.to_owned()) {
+            self.avg_start_position.push(start);
+        }
+
+        self.red_zone_trips_per_game
+            .push(game.red_zone_trips(team.to_owned()));
+
+        if let Some(scoreboard) = game.scoreboard() {
+            self.points_per_game.push(scoreboard.points(&team));
+
+            for quarter in [
+                Quarter::First,
+                Quarter::Second,
+                Quarter::Third,
+                Quarter::Fourth,
+            ] {
+                self.points_per_quarter
+                    .push(scoreboard.points_in(&team, &quarter));
+            }
+        }
+
+        if !game.flags.contains(&Flags::IgnoreActions) {
+            for drive in game.team_drives(team.to_owned()) {
+                for play in drive.plays() {
+                    if play.action.is_unknown() {
+                        continue;
+                    }
+
+                    add_one(&mut self.play_counts, play.action.to_owned());
+
+                    if let Some(key) = play.action.key() {
+                        add_one(&mut self.key_counts, key)
+                    }
+
+                    if let Some(playset) = play.action.playset() {
+                        add_one(&mut self.playset_counts, playset)
+                    }
+                }
+            }
+
+            (self.most_common_play, self.least_common_play) = extremes(&self.play_counts);
+            (self.most_common_key, self.least_common_key) = extremes(&self.key_counts);
+            (self.most_common_playset, self.least_common_playset) = extremes(&self.playset_counts);
+        }
+
+        for down in game.first_downs(team.to_owned()) {
+            add_one(&mut self.first_downs_by_down, down)
+        }
+
+        self.time_to_first_down = median_down(&self.first_downs_by_down);
+
+        let conversions = game.conversion_counts(team);
+        let rate = |down: &Down| match conversions_on(&conversions, down) {
+            (_, 0) => None,
+            (converted, attempts) => Some(converted as f32 / attempts as f32),
+        };
+
+        self.third_down_rate_per_game.push(rate(&Down::Third));
+        self.fourth_down_rate_per_game.push(rate(&Down::Fourth));
+
+        for (total, count) in self.conversions.iter_mut().zip(conversions) {
+            total.conversions += count.conversions;
+            total.attempts += count.attempts;
+        }
+    }
+
+    /// Summarises every delta over every game.
+    pub fn delta_summary(&self) -> Option<Summary> {
+        Summary::from_counts(&self.deltas)
+    }
+
+    /// Summarises every gain over every game.
+    pub fn gain_summary(&self) -> Option<Summary> {
+        Summary::from_counts(&gains(&self.deltas))
+    }
+
+    /// Summarises every loss over every game.
+    pub fn loss_summary(&self) -> Option<Summary> {
+        Summary::from_counts(&losses(&self.deltas))
+    }
+
+    /// Summarises the number of downs each first down took.
+    pub fn downs_to_convert_summary(&self) -> Option<Summary> {
+        let downs: Vec<u8> = self
+            .first_downs_by_down
+            .iter()
+            .flat_map(|(down, count)| std::iter::repeat_n(down.number(), *count))
+            .collect();
+
+        Summary::from_counts(&downs)
+    }
+
+    /// Summarises the plays in every regulation quarter over every game.
+    pub fn plays_per_quarter_summary(&self) -> Option<Summary> {
+        let plays: Vec<f32> = self
+            .quarterly_plays
+            .iter()
+            .map(|plays| *plays as f32)
+            .collect();
+
+        Summary::new(&plays)
+    }
+}
+
+/// Builds stats for every team in `log`, in the order they first appear.
+/// Teams are left out of the games they are ignored in.
+pub fn team_stats(log: &LogFile) -> Vec<TeamStats> {
+    let mut teams: Vec<Team> = vec![];
+
+    for game in &log.0 {
+        for team in game.teams().unwrap_or_default() {
+            if !teams.contains(&team) {
+                teams.push(team)
+            }
+        }
+    }
+
+    teams
+        .into_iter()
+        .map(|team| TeamStats::from_log(log, team))
+        .collect()
+}
+
+/// The positive values of `deltas`.
+pub(crate) fn gains(deltas: &[i8]) -> Vec<i8> {
+    deltas
+        .iter()
+        .filter(|delta| delta.is_positive())
+        .copied()
+        .collect()
+}
+
+/// The negative values of `deltas`.
+pub(crate) fn losses(deltas: &[i8]) -> Vec<i8> {
+    deltas
+        .iter()
+        .filter(|delta| delta.is_negative())
+        .copied()
+        .collect()
+}
+
+fn add_one<T: PartialEq>(counts: &mut Vec<(T, usize)>, value: T) {
+    match counts.iter_mut().find(|(counted, _)| *counted == value) {
+        Some(total) => total.1 += 1,
+        None => counts.push((value, 1)),
+    }
+}
+
+fn add_counts<T: PartialEq>(totals: &mut Vec<(T, usize)>, counts: Vec<(T, usize)>) {
+    for (value, count) in counts {
+        match totals.iter_mut().find(|(counted, _)| *counted == value) {
+            Some(total) => total.1 += count,
+            None => totals.push((value, count)),
+        }
+    }
+}
+
+/// The most and least frequent of `counts`, ties going to whichever came first.
+fn extremes<T: Clone>(counts: &[(T, usize)]) -> (Option<T>, Option<T>) {
+    let mut most: Option<&(T, usize)> = None;
+    let mut least: Option<&(T, usize)> = None;
+
+    for entry in counts {
+        if most.is_none_or(|most| entry.1 > most.1) {
+            most = Some(entry)
+        }
+
+        if least.is_none_or(|least| entry.1 < least.1) {
+            least = Some(entry)
+        }
+    }
+
+    (
+        most.map(|(value, _)| value.to_owned()),
+        least.map(|(value, _)| value.to_owned()),
+    )
+}
+
+/// The median of downs counted in `counts`, taking the lower of two middles.
+fn median_down(counts: &[(Down, usize)]) -> Option<Down> {
+    let total: usize = counts.iter().map(|(_, count)| count).sum();
+
+    if total == 0 {
+        return None;
+    }
+
+    let mut seen = 0;
+
+    for down in DOWNS {
+        seen += counts
+            .iter()
+            .find(|(counted, _)| *counted == down)
+            .map_or(0, |(_, count)| *count);
+
+        if seen * 2 >= total {
+            return Some(down);
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::*;
+    use crate::testing::gamelog;
+
+    #[test]
+    fn team_stats() {
+        let stats = super::team_stats(&gamelog());
+
+        assert!(
+            stats
+                .iter()
+                .map(|stats| stats.team.to_owned())
+                .collect::<Vec<Team>>()
+                == vec![
+                    Team::ArizonaState,
+                    Team::TexasAnM,
+                    Team::Syracuse,
+                    Team::Colorado,
+                    Team::Nebraska,
+                    Team::SouthCarolina,
+                    Team::Iowa,
+                ]
+        );
+
+        // Every per-game field has one entry per game played.
+        for team in &stats {
+            let games = team.plays_per_game.len();
+
+            assert!(team.avg_terrain_gain.len() == games);
+            assert!(team.penalties_per_game.len() == games);
+            assert!(team.turnover_margin_per_game.len() == games);
+            assert!(team.points_per_quarter.len() == team.points_per_game.len() * 4);
+            assert!(team.most_common_play.is_some() && team.time_to_first_down.is_some());
+        }
+    }
+
+    #[test]
+    fn nebraska() {
+        let nebraska = TeamStats::from_log(&gamelog(), Team::Nebraska);
+
+        assert!(nebraska.plays_per_game == vec![15, 28, 18, 20]);
+        assert!(nebraska.points_per_game == vec![6, 6, 8, 7]);
+        assert!(nebraska.penalty_yards_per_game == vec![0, 0, 0, 30]);
+
+        // Tied with SlotOut, but called first.
+        assert!(nebraska.most_common_play == Some(Action::PlayActionComebacks));
+        assert!(nebraska.least_common_play == Some(Action::HalfbackSweep));
+        assert!(nebraska.most_common_key == Some(Key::Square));
+        assert!(nebraska.least_common_key == Some(Key::X));
+        assert!(nebraska.time_to_first_down == Some(Down::First));
+
+        let (converted, attempts) = conversions_on(&nebraska.conversions, &Down::Third);
+
+        assert!(converted <= attempts && attempts > 0);
+        assert!(nebraska.third_down_rate_per_game.len() == nebraska.points_per_game.len());
+
+        let downs = nebraska.downs_to_convert_summary().unwrap();
+
+        assert!(downs.median.floor() as u8 == nebraska.time_to_first_down.unwrap().number());
+        assert!(downs.min >= 1.0 && downs.max <= 4.0);
+    }
+
+    #[test]
+    fn summaries() {
+        let log = gamelog();
+        let arizona = TeamStats::from_log(&log, Team::ArizonaState);
+
+        // No losses in its second game.
+        assert!(arizona.avg_terrain_loss[1].is_none());
+        assert!(log.0[8].loss_summary(Team::ArizonaState).is_none());
+
+        let gains = arizona.gain_summary().unwrap();
+        let deltas = arizona.delta_summary().unwrap();
+
+        assert!(deltas.n == arizona.deltas.len());
+        assert!(gains.n + arizona.loss_summary().unwrap().n <= deltas.n);
+        assert!(gains.min > 0.0 && gains.q1 <= gains.median && gains.median <= gains.q3);
+        assert!(arizona.plays_per_quarter_summary().unwrap().n == 8);
+    }
+
+    #[test]
+    fn skipped() {
+        let log = gamelog();
+
+        // Ignored in one of its games, and scores ignored in another.
+        let texas = TeamStats::from_log(&log, Team::TexasAnM);
+
+        assert!(texas.plays_per_game.len() == 2);
+        assert!(texas.points_per_game == vec![15]);
+
+        // Games without the team are skipped.
+        let mut colorado = TeamStats::new(Team::Colorado);
+
+        colorado.add_game(&log.0[0]);
+
+        assert!(colorado == TeamStats::new(Team::Colorado));
+    }
+}
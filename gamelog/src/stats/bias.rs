@@ -0,0 +1,205 @@
+use crate::{
+    Action, DistanceToGo, Down, DownAndDistance, Flags, LogFile, Team,
+    stats::{ChiSquare, ConfidenceInterval, DOWNS, chi_square_independence, wilson_interval},
+};
+use std::fmt;
+use strum::{EnumIter, IntoEnumIterator};
+
+/// A kind of play call a team may lean towards.
+#[derive(Debug, Clone, PartialEq, EnumIter)]
+pub enum Bias {
+    Run,
+    PlayAction,
+    Halfback,
+}
+
+impl Bias {
+    /// Returns `true` if `action` is of this kind.
+    pub fn matches(&self, action: &Action) -> bool {
+        match self {
+            Self::Run => action.is_run(),
+            Self::PlayAction => action.is_play_action(),
+            Self::Halfback => action.is_halfback(),
+        }
+    }
+}
+
+/// How often a team made one kind of call in one situation, against the league.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BiasRate {
+    pub bias: Bias,
+    /// `None` for every down.
+    pub down: Option<Down>,
+    /// `None` for every distance.
+    pub distance: Option<DistanceToGo>,
+    /// Calls of this kind.
+    pub calls: usize,
+    /// Known calls of any kind.
+    pub plays: usize,
+    /// `calls` over `plays`, with a 95% interval.
+    pub rate: Option<ConfidenceInterval>,
+    /// Calls of this kind by every team, this one included.
+    pub league_calls: usize,
+    pub league_plays: usize,
+    /// Whether the team's rate differs from every other team's together.
+    pub against_league: Option<ChiSquare>,
+}
+
+impl BiasRate {
+    pub fn league_rate(&self) -> Option<f32> {
+        match self.league_plays {
+            0 => None,
+            plays => Some(self.league_calls as f32 / plays as f32),
+        }
+    }
+}
+
+impl fmt::Display for BiasRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.bias)?;
+
+        match (&self.down, &self.distance) {
+            (Some(down), Some(distance)) => write!(f, " on {:?} & {:?}", down, distance)?,
+            (Some(down), None) => write!(f, " on {:?}", down)?,
+            (None, Some(distance)) => write!(f, " at {:?}", distance)?,
+            (None, None) => (),
+        }
+
+        write!(f, ": {}/{}", self.calls, self.plays)?;
+
+        if let Some(rate) = &self.rate {
+            write!(f, ", {}", rate)?;
+        }
+
+        if let Some(league) = self.league_rate() {
+            write!(f, ", league {:.2}", league)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `team`'s rate of each kind of call over `log`: overall, by down, by
+/// distance to go, and by both, in that order.
+///
+/// Only known actions count, and games flagged `Flags::IgnoreActions`
+/// are left out. Teams are left out of the games they are ignored in.
+pub fn bias_rates(log: &LogFile, team: Team) -> Vec<BiasRate> {
+    let mut calls: Vec<(Team, DownAndDistance, Action)> = vec![];
+
+    for game in &log.0 {
+        if game.flags.contains(&Flags::IgnoreActions) {
+            continue;
+        }
+
+        for caller in game.teams().unwrap_or_default() {
+            for (facing, play) in game.faced_plays(caller.to_owned()).into_iter().flatten() {
+                if !play.action.is_unknown() {
+                    calls.push((caller.to_owned(), facing, play.action))
+                }
+            }
+        }
+    }
+
+    let mut situations: Vec<(Option<Down>, Option<DistanceToGo>)> = vec![(None, None)];
+
+    situations.extend(DOWNS.into_iter().map(|down| (Some(down), None)));
+    situations.extend(DistanceToGo::iter().map(|distance| (None, Some(distance))));
+    situations.extend(DOWNS.into_iter().flat_map(|down| {
+        DistanceToGo::iter().map(move |distance| (Some(down.to_owned()), Some(distance)))
+    }));
+
+    let mut rates = vec![];
+
+    for bias in Bias::iter() {
+        for (down, distance) in &situations {
+            // (calls, plays) by this team, and by every other team.
+            let mut own = (0, 0);
+            let mut rest = (0, 0);
+
+            for (caller, facing, action) in &calls {
+                if down.is_some() && facing.down != *down
+                    || distance.is_some() && facing.distance() != *distance
+                {
+                    continue;
+                }
+
+                let counts = if *caller == team { &mut own } else { &mut rest };
+
+                counts.1 += 1;
+
+                if bias.matches(action) {
+                    counts.0 += 1
+                }
+            }
+
+            rates.push(BiasRate {
+                bias: bias.to_owned(),
+                down: down.to_owned(),
+                distance: distance.to_owned(),
+                calls: own.0,
+                plays: own.1,
+                rate: wilson_interval(own.0, own.1, 0.95),
+                league_calls: own.0 + rest.0,
+                league_plays: own.1 + rest.1,
+                against_league: chi_square_independence(&[
+                    vec![own.0, own.1 - own.0],
+                    vec![rest.0, rest.1 - rest.0],
+                ]),
+            })
+        }
+    }
+
+    rates
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::gamelog;
+    use crate::{stats::*, *};
+    use strum::IntoEnumIterator;
+
+    #[test]
+    fn bias_rates() {
+        let log = gamelog();
+
+        let rates = super::bias_rates(&log, Team::Nebraska);
+        let calls = play_calls(&log, Team::Nebraska);
+
+        // Overall, four downs, three distances and twelve of both, per bias.
+        assert!(rates.len() == 3 * 20);
+
+        for bias in [Bias::Run, Bias::PlayAction, Bias::Halfback] {
+            let overall = rates
+                .iter()
+                .find(|rate| rate.bias == bias && rate.down.is_none() && rate.distance.is_none())
+                .unwrap();
+            let expected: usize = calls
+                .iter()
+                .filter(|call| bias.matches(&call.choice))
+                .map(|call| call.count)
+                .sum();
+
+            assert!(overall.calls == expected);
+            assert!(overall.plays == calls.iter().map(|call| call.count).sum());
+            assert!(overall.league_plays > overall.plays);
+
+            let rate = overall.rate.as_ref().unwrap();
+
+            assert!(rate.lower <= rate.estimate && rate.estimate <= rate.upper);
+
+            // Plays with an unknown down only count overall.
+            let by_down: usize = rates
+                .iter()
+                .filter(|rate| rate.bias == bias && rate.down.is_some() && rate.distance.is_none())
+                .map(|rate| rate.plays)
+                .sum();
+
+            assert!(by_down <= overall.plays);
+        }
+
+        // Only three of the actions are halfback plays.
+        assert!(Action::iter().filter(|action| action.is_halfback()).count() == 3);
+        assert!(!Action::Unknown.is_pass() && !Action::Unknown.is_run());
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    Action, LogFile, Team,
+    stats::{
+        ChiSquare, TTest, TeamStats, chi_square_goodness_of_fit, chi_square_independence,
+        contingency_table, team_stats, welch_t_test,
+    },
+};
+
+/// Tests of whether two teams play differently, over every game of a LogFile.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub a: TeamStats,
+    pub b: TeamStats,
+    /// `a` against `b`, from `Game::deltas`.
+    pub delta: Option<TTest>,
+    pub gain: Option<TTest>,
+    pub loss: Option<TTest>,
+    /// Whether the two teams call plays in different proportions.
+    pub play_calls: Option<ChiSquare>,
+    /// Whether `a`'s play calls differ in proportion from every team's together.
+    pub a_against_league: Option<ChiSquare>,
+    /// Whether `b`'s play calls differ in proportion from every team's together.
+    pub b_against_league: Option<ChiSquare>,
+}
+
+impl Comparison {
+    pub fn new(log: &LogFile, a: Team, b: Team) -> Self {
+        let a = TeamStats::from_log(log, a);
+        let b = TeamStats::from_log(log, b);
+
+        let test = |a: Option<_>, b: Option<_>| welch_t_test(&a?, &b?);
+
+        let (_, table) = contingency_table(&[&a.play_counts[..], &b.play_counts[..]]);
+
+        let league: Vec<(Action, usize)> = {
+            let stats = team_stats(log);
+            let rows: Vec<&[(Action, usize)]> =
+                stats.iter().map(|team| &team.play_counts[..]).collect();
+            let (actions, table) = contingency_table(&rows);
+
+            actions
+                .into_iter()
+                .enumerate()
+                .map(|(idx, action)| (action, table.iter().map(|row| row[idx]).sum()))
+                .collect()
+        };
+
+        let against_league = |team: &TeamStats| {
+            let (_, table) = contingency_table(&[&league[..], &team.play_counts[..]]);
+            let expected: Vec<f32> = table[0].iter().map(|count| *count as f32).collect();
+
+            chi_square_goodness_of_fit(&table[1], &expected)
+        };
+
+        Self {
+            delta: test(a.delta_summary(), b.delta_summary()),
+            gain: test(a.gain_summary(), b.gain_summary()),
+            loss: test(a.loss_summary(), b.loss_summary()),
+            play_calls: chi_square_independence(&table),
+            a_against_league: against_league(&a),
+            b_against_league: against_league(&b),
+            a,
+            b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::*;
+    use crate::testing::gamelog;
+
+    #[test]
+    fn compare() {
+        let log = gamelog();
+
+        let comparison = Comparison::new(&log, Team::Nebraska, Team::Iowa);
+        let delta = comparison.delta.unwrap();
+
+        let nebraska = comparison.a.delta_summary().unwrap();
+        let iowa = comparison.b.delta_summary().unwrap();
+
+        assert!(delta.difference.estimate == nebraska.mean - iowa.mean);
+        assert!((0.0..=1.0).contains(&delta.p_value));
+        assert!(comparison.play_calls.is_some());
+        // Every action called by any team is a category.
+        assert!(comparison.a_against_league.unwrap().df >= comparison.a.play_counts.len() - 1);
+
+        // Iowa is not in the third game.
+        let absent = Comparison::new(
+            &LogFile(vec![log.0[2].to_owned()]),
+            Team::Nebraska,
+            Team::Iowa,
+        );
+
+        assert!(absent.delta.is_none() && absent.play_calls.is_none());
+        assert!(absent.b_against_league.is_none());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{DistanceToGo, Down, DownAndDistance};
+use strum::IntoEnumIterator;
+
+/// A play on third or fourth down, and whether it gained a new first down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub faced: DownAndDistance,
+    pub converted: bool,
+}
+
+/// A team's third or fourth down plays from one distance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionCount {
+    pub down: Down,
+    /// `None` where the distance was not known, including at the goal line.
+    pub distance: Option<DistanceToGo>,
+    pub conversions: usize,
+    pub attempts: usize,
+}
+
+impl ConversionCount {
+    /// Every distance on third and fourth down, with nothing counted yet.
+    pub fn empty() -> Vec<Self> {
+        [Down::Third, Down::Fourth]
+            .into_iter()
+            .flat_map(|down| {
+                DistanceToGo::iter()
+                    .map(Some)
+                    .chain([None])
+                    .map(move |distance| Self {
+                        down: down.to_owned(),
+                        distance,
+                        conversions: 0,
+                        attempts: 0,
+                    })
+            })
+            .collect()
+    }
+
+    /// Counts `conversions` into `counts`, which should come from `ConversionCount::empty`.
+    pub fn add(counts: &mut [Self], conversions: &[Conversion]) {
+        for conversion in conversions {
+            let distance = conversion.faced.distance();
+
+            if let Some(count) = counts.iter_mut().find(|count| {
+                Some(&count.down) == conversion.faced.down.as_ref() && count.distance == distance
+            }) {
+                count.attempts += 1;
+
+                if conversion.converted {
+                    count.conversions += 1
+                }
+            }
+        }
+    }
+
+    /// The fraction of attempts converted, `None` if there were none.
+    pub fn rate(&self) -> Option<f32> {
+        if self.attempts == 0 {
+            return None;
+        }
+
+        Some(self.conversions as f32 / self.attempts as f32)
+    }
+}
+
+/// Totals `counts` over every distance of `down`.
+pub fn conversions_on(counts: &[ConversionCount], down: &Down) -> (usize, usize) {
+    counts.iter().filter(|count| count.down == *down).fold(
+        (0, 0),
+        |(conversions, attempts), count| {
+            (conversions + count.conversions, attempts + count.attempts)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::play;
+    use crate::{stats::*, *};
+
+    #[test]
+    fn conversions() {
+        let a = Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events: vec![
+                Event::Kickoff(Team::Nebraska, None, None),
+                play(Action::Unknown, Down::Second, TerrainState::Yards(5)),
+                play(Action::Unknown, Down::Third, TerrainState::Yards(2)),
+                // Converted on 3rd & 2.
+                play(Action::Unknown, Down::First, TerrainState::Yards(10)),
+                play(Action::Unknown, Down::Second, TerrainState::Yards(8)),
+                play(Action::Unknown, Down::Third, TerrainState::Yards(8)),
+                // Not on 3rd & 8, then failed on 4th & 8,
+                // the log noting the new offence's 1st & 10.
+                play(Action::Unknown, Down::Fourth, TerrainState::Yards(8)),
+                play(Action::Unknown, Down::First, TerrainState::Yards(10)),
+                Event::Turnover(Team::Iowa, Some(TurnoverKind::Downs)),
+                play(Action::Unknown, Down::Second, TerrainState::Yards(10)),
+                play(Action::Unknown, Down::Third, TerrainState::Yards(4)),
+                // Scored on 3rd & 4.
+                play(Action::Unknown, None, TerrainState::Yards(0)),
+                Event::Score(ScorePoints::Touchdown, None),
+                Event::Kickoff(Team::Nebraska, None, None),
+            ],
+        };
+
+        let nebraska = a.conversions(Team::Nebraska);
+
+        assert!(nebraska.len() == 3);
+        assert!(nebraska[0].converted && nebraska[0].faced.distance() == Some(DistanceToGo::Short));
+        assert!(!nebraska[1].converted && !nebraska[2].converted);
+        assert!(nebraska[2].faced.down == Some(Down::Fourth));
+
+        let iowa = a.conversions(Team::Iowa);
+
+        assert!(iowa.len() == 1 && iowa[0].converted);
+
+        let counts = a.conversion_counts(Team::Nebraska);
+
+        assert!(counts.len() == 8);
+        assert!(conversions_on(&counts, &Down::Third) == (1, 2));
+        assert!(conversions_on(&counts, &Down::Fourth) == (0, 1));
+
+        let long = counts
+            .iter()
+            .find(|count| count.down == Down::Third && count.distance == Some(DistanceToGo::Long))
+            .unwrap();
+
+        assert!(long.rate() == Some(0.0));
+        assert!(counts.last().unwrap().rate().is_none());
+    }
+}
@@ -0,0 +1,305 @@
+use crate::{Action, Down, DownAndDistance, Flags, Game, LogFile, Team, TerrainState};
+use std::fmt;
+use strum::IntoEnumIterator;
+
+/// Yards a run must gain to be explosive.
+pub const EXPLOSIVE_RUN: i8 = 10;
+/// Yards a pass must gain to be explosive.
+pub const EXPLOSIVE_PASS: i8 = 15;
+
+/// Returns whether a play facing `faced` that made `delta` was a success:
+/// gaining 40% of the yards needed on first down, 60% on second, and all of
+/// them on third and fourth. `None` where the down or distance is unknown.
+pub fn is_success(faced: &DownAndDistance, delta: i8) -> Option<bool> {
+    let needed = match faced.terrain.as_ref()? {
+        TerrainState::Yards(yards) => *yards as f32,
+        TerrainState::Inches => 1.0,
+        TerrainState::GoalLine | TerrainState::Unknown => return None,
+    };
+
+    let share = match faced.down.as_ref()? {
+        Down::First => 0.4,
+        Down::Second => 0.6,
+        Down::Third | Down::Fourth => 1.0,
+    };
+
+    Some(delta as f32 >= share * needed)
+}
+
+/// How consistently, and how explosively, plays gained yards.
+///
+/// Deltas are changes in yards-to-go, so a play that gains a first down only
+/// counts the yards that were needed. Explosive plays are undercounted for it.
+/// Plays of unknown actions only count towards success.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Efficiency {
+    /// Plays whose success could be judged.
+    pub plays: usize,
+    pub successes: usize,
+    /// Runs with a known delta.
+    pub runs: usize,
+    pub explosive_runs: usize,
+    /// Runs that gained nothing, or lost yards.
+    pub stuffed_runs: usize,
+    /// Passes with a known delta.
+    pub passes: usize,
+    pub explosive_passes: usize,
+}
+
+impl Efficiency {
+    /// Adds a play calling `action` facing `faced`, that made `delta`.
+    pub fn add(&mut self, faced: &DownAndDistance, action: &Action, delta: Option<i8>) {
+        let Some(delta) = delta else {
+            return;
+        };
+
+        if let Some(success) = is_success(faced, delta) {
+            self.plays += 1;
+
+            if success {
+                self.successes += 1
+            }
+        }
+
+        if action.is_run() {
+            self.runs += 1;
+
+            if delta >= EXPLOSIVE_RUN {
+                self.explosive_runs += 1
+            }
+
+            if delta <= 0 {
+                self.stuffed_runs += 1
+            }
+        } else if action.is_pass() {
+            self.passes += 1;
+
+            if delta >= EXPLOSIVE_PASS {
+                self.explosive_passes += 1
+            }
+        }
+    }
+
+    /// Adds up `self` and `other`.
+    pub fn merge(&mut self, other: &Self) {
+        self.plays += other.plays;
+        self.successes += other.successes;
+        self.runs += other.runs;
+        self.explosive_runs += other.explosive_runs;
+        self.stuffed_runs += other.stuffed_runs;
+        self.passes += other.passes;
+        self.explosive_passes += other.explosive_passes;
+    }
+
+    pub fn success_rate(&self) -> Option<f32> {
+        rate(self.successes, self.plays)
+    }
+
+    /// Fraction of runs and passes that were explosive.
+    pub fn explosive_rate(&self) -> Option<f32> {
+        rate(
+            self.explosive_runs + self.explosive_passes,
+            self.runs + self.passes,
+        )
+    }
+
+    pub fn stuff_rate(&self) -> Option<f32> {
+        rate(self.stuffed_runs, self.runs)
+    }
+}
+
+impl fmt::Display for Efficiency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.success_rate() {
+            Some(rate) => write!(
+                f,
+                "success {}/{} ({:.1}%)",
+                self.successes,
+                self.plays,
+                rate * 100.0
+            )?,
+            None => write!(f, "success n/a")?,
+        }
+
+        write!(
+            f,
+            ", explosive runs {}/{}, explosive passes {}/{}",
+            self.explosive_runs, self.runs, self.explosive_passes, self.passes
+        )?;
+
+        if let Some(rate) = self.stuff_rate() {
+            write!(f, ", stuffed {:.1}%", rate * 100.0)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn rate(count: usize, total: usize) -> Option<f32> {
+    match total {
+        0 => None,
+        total => Some(count as f32 / total as f32),
+    }
+}
+
+/// `team`'s efficiency in each game of `log` it played, in order,
+/// with the index into `LogFile.0` of each.
+pub fn efficiency_by_game(log: &LogFile, team: Team) -> Vec<(usize, Efficiency)> {
+    log.0
+        .iter()
+        .enumerate()
+        .filter(|(_, game)| game.teams().unwrap_or_default().contains(&team))
+        .map(|(idx, game)| (idx, game_efficiency(game, team.to_owned())))
+        .collect()
+}
+
+/// `team`'s efficiency over every game of `log`.
+pub fn efficiency(log: &LogFile, team: Team) -> Efficiency {
+    efficiency_by_game(log, team)
+        .iter()
+        .fold(Efficiency::default(), |mut total, (_, game)| {
+            total.merge(game);
+            total
+        })
+}
+
+/// `team`'s efficiency calling each known action over `log`.
+/// Games flagged `Flags::IgnoreActions` are left out.
+pub fn efficiency_by_action(log: &LogFile, team: Team) -> Vec<(Action, Efficiency)> {
+    let mut actions: Vec<(Action, Efficiency)> = Action::iter()
+        .filter(|action| !action.is_unknown())
+        .map(|action| (action, Efficiency::default()))
+        .collect();
+
+    for game in &log.0 {
+        if game.flags.contains(&Flags::IgnoreActions)
+            || !game.teams().unwrap_or_default().contains(&team)
+        {
+            continue;
+        }
+
+        for (faced, play, delta) in game.faced_deltas(team.to_owned()) {
+            if let Some((_, efficiency)) = actions
+                .iter_mut()
+                .find(|(action, _)| *action == play.action)
+            {
+                efficiency.add(&faced, &play.action, delta)
+            }
+        }
+    }
+
+    actions
+}
+
+/// Games flagged `Flags::IgnoreActions` only count towards success.
+fn game_efficiency(game: &Game, team: Team) -> Efficiency {
+    let mut efficiency = Efficiency::default();
+    let ignore_actions = game.flags.contains(&Flags::IgnoreActions);
+
+    for (faced, play, delta) in game.faced_deltas(team) {
+        let action = if ignore_actions {
+            &Action::Unknown
+        } else {
+            &play.action
+        };
+
+        efficiency.add(&faced, action, delta)
+    }
+
+    efficiency
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{gamelog, play};
+    use crate::{stats::*, *};
+
+    fn faced(down: Down, yards: u8) -> DownAndDistance {
+        DownAndDistance {
+            down: Some(down),
+            terrain: Some(TerrainState::Yards(yards)),
+        }
+    }
+
+    #[test]
+    fn is_success() {
+        assert!(super::is_success(&faced(Down::First, 10), 4) == Some(true));
+        assert!(super::is_success(&faced(Down::First, 10), 3) == Some(false));
+        assert!(super::is_success(&faced(Down::Second, 5), 3) == Some(true));
+        assert!(super::is_success(&faced(Down::Second, 10), 5) == Some(false));
+        assert!(super::is_success(&faced(Down::Third, 2), 1) == Some(false));
+        assert!(super::is_success(&faced(Down::Fourth, 2), 2) == Some(true));
+
+        let goal_line = DownAndDistance {
+            down: Some(Down::First),
+            terrain: Some(TerrainState::GoalLine),
+        };
+
+        assert!(super::is_success(&goal_line, 0).is_none());
+    }
+
+    #[test]
+    fn efficiency() {
+        let mut a = Efficiency::default();
+
+        a.add(&faced(Down::First, 10), &Action::PowerZero, Some(10));
+        a.add(&faced(Down::First, 10), &Action::HalfbackSlam, Some(-2));
+        a.add(&faced(Down::Second, 12), &Action::Curls, Some(2));
+        a.add(&faced(Down::Third, 20), &Action::HailMary, Some(20));
+        a.add(&faced(Down::First, 10), &Action::Unknown, Some(5));
+        a.add(&faced(Down::First, 10), &Action::Mesh, None);
+
+        assert!(a.plays == 5 && a.successes == 3);
+        assert!(a.runs == 2 && a.explosive_runs == 1 && a.stuffed_runs == 1);
+        assert!(a.passes == 2 && a.explosive_passes == 1);
+        assert!(a.explosive_rate() == Some(0.5));
+        assert!(a.stuff_rate() == Some(0.5));
+
+        let log = gamelog();
+
+        let total = super::efficiency(&log, Team::Nebraska);
+        let games = efficiency_by_game(&log, Team::Nebraska);
+        let actions = efficiency_by_action(&log, Team::Nebraska);
+
+        assert!(games.iter().map(|(_, game)| game.plays).sum::<usize>() == total.plays);
+        assert!(
+            games
+                .iter()
+                .all(|(idx, _)| log.0[*idx].teams().unwrap().contains(&Team::Nebraska))
+        );
+        assert!(actions.iter().map(|(_, action)| action.runs).sum::<usize>() <= total.runs);
+        assert!(total.successes <= total.plays && total.success_rate().is_some());
+    }
+
+    #[test]
+    fn ignore_actions() {
+        let game = |flags: Vec<Flags>| Game {
+            version: crate::MIN_VER,
+            flags,
+            events: vec![
+                Event::Kickoff(Team::Nebraska, None, None),
+                play(Action::PowerZero, Down::Second, TerrainState::Yards(8)),
+                play(Action::HalfbackSlam, Down::First, TerrainState::Yards(10)),
+                play(Action::Curls, Down::Second, TerrainState::Yards(12)),
+                Event::Kickoff(Team::Iowa, None, None),
+            ],
+        };
+        let log = LogFile(vec![game(vec![]), game(vec![Flags::IgnoreActions])]);
+
+        let games = efficiency_by_game(&log, Team::Nebraska);
+        let (known, ignored) = (&games[0].1, &games[1].1);
+
+        assert!(games.iter().map(|(idx, _)| *idx).eq([0, 1]));
+        assert!(known.runs == 2 && known.passes == 1);
+        assert!(ignored.runs == 0 && ignored.passes == 0);
+        assert!(known.plays > 0 && ignored.plays == known.plays);
+        assert!(ignored.successes == known.successes);
+        assert!(
+            efficiency_by_action(&log, Team::Nebraska)
+                .iter()
+                .map(|(_, efficiency)| efficiency.runs)
+                .sum::<usize>()
+                == known.runs
+        );
+    }
+}
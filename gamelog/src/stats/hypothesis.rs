@@ -0,0 +1,535 @@
+use crate::stats::Summary;
+use std::fmt;
+
+/// A range that should contain the true value `level` of the time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfidenceInterval {
+    pub estimate: f32,
+    pub lower: f32,
+    pub upper: f32,
+    /// Between 0 and 1, e.g. `0.95`.
+    pub level: f32,
+}
+
+impl fmt::Display for ConfidenceInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:.2} ({}% CI {:.2} to {:.2})",
+            self.estimate,
+            (self.level * 100.0).round(),
+            self.lower,
+            self.upper
+        )
+    }
+}
+
+/// The result of Welch's two-sample t-test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TTest {
+    pub t: f32,
+    /// Welch–Satterthwaite degrees of freedom.
+    pub df: f32,
+    /// Two-sided.
+    pub p_value: f32,
+    /// The first mean minus the second, with a 95% interval.
+    pub difference: ConfidenceInterval,
+    /// Cohen's d, against the average of both variances.
+    pub effect_size: f32,
+}
+
+impl fmt::Display for TTest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "t {:.2}, df {:.1}, p {:.4}, difference {}, d {:.2}",
+            self.t, self.df, self.p_value, self.difference, self.effect_size
+        )
+    }
+}
+
+/// The result of a chi-square test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChiSquare {
+    pub statistic: f32,
+    pub df: usize,
+    pub p_value: f32,
+    /// Cohen's w for goodness of fit, Cramér's V for independence.
+    pub effect_size: f32,
+}
+
+impl fmt::Display for ChiSquare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "chi-square {:.2}, df {}, p {:.4}, effect size {:.2}",
+            self.statistic, self.df, self.p_value, self.effect_size
+        )
+    }
+}
+
+/// Tests whether the means behind `a` and `b` differ, without assuming equal variances.
+/// Returns `None` if either has fewer than two samples, or neither varies.
+pub fn welch_t_test(a: &Summary, b: &Summary) -> Option<TTest> {
+    let (va, vb) = (a.variance? as f64, b.variance? as f64);
+    let (na, nb) = (a.n as f64, b.n as f64);
+
+    let (ea, eb) = (va / na, vb / nb);
+    let se = (ea + eb).sqrt();
+
+    if se == 0.0 {
+        return None;
+    }
+
+    let difference = (a.mean - b.mean) as f64;
+    let t = difference / se;
+    let df = (ea + eb).powi(2) / (ea.powi(2) / (na - 1.0) + eb.powi(2) / (nb - 1.0));
+    let margin = t_critical(df, 0.95) * se;
+
+    Some(TTest {
+        t: t as f32,
+        df: df as f32,
+        p_value: t_p_value(t, df) as f32,
+        difference: ConfidenceInterval {
+            estimate: difference as f32,
+            lower: (difference - margin) as f32,
+            upper: (difference + margin) as f32,
+            level: 0.95,
+        },
+        effect_size: (difference / ((va + vb) / 2.0).sqrt()) as f32,
+    })
+}
+
+/// Tests whether `observed` counts follow the proportions of `expected`,
+/// which need not sum to one.
+///
+/// Returns `None` with fewer than two categories or no observations,
+/// or if anything was observed where nothing was expected.
+pub fn chi_square_goodness_of_fit(observed: &[usize], expected: &[f32]) -> Option<ChiSquare> {
+    if observed.len() != expected.len() {
+        return None;
+    }
+
+    let n = observed.iter().sum::<usize>() as f64;
+    let total_weight = expected.iter().map(|weight| *weight as f64).sum::<f64>();
+
+    if n == 0.0 || total_weight <= 0.0 {
+        return None;
+    }
+
+    let mut statistic = 0.0;
+    let mut categories = 0;
+
+    for (observed, weight) in observed.iter().zip(expected) {
+        let expected = *weight as f64 / total_weight * n;
+        let observed = *observed as f64;
+
+        if expected <= 0.0 {
+            if observed > 0.0 {
+                return None;
+            }
+
+            continue;
+        }
+
+        statistic += (observed - expected).powi(2) / expected;
+        categories += 1;
+    }
+
+    if categories < 2 {
+        return None;
+    }
+
+    let df = categories - 1;
+
+    Some(ChiSquare {
+        statistic: statistic as f32,
+        df,
+        p_value: chi_square_p_value(statistic, df as f64) as f32,
+        effect_size: (statistic / n).sqrt() as f32,
+    })
+}
+
+/// Tests whether the rows and columns of a contingency `table` are independent.
+/// Empty rows and columns are left out; `None` if fewer than two of either remain.
+pub fn chi_square_independence(table: &[Vec<usize>]) -> Option<ChiSquare> {
+    let columns = table.iter().map(|row| row.len()).max()?;
+    let cell = |row: &Vec<usize>, column: usize| *row.get(column).unwrap_or(&0) as f64;
+
+    let rows: Vec<&Vec<usize>> = table
+        .iter()
+        .filter(|row| row.iter().sum::<usize>() > 0)
+        .collect();
+    let columns: Vec<usize> = (0..columns)
+        .filter(|column| rows.iter().any(|row| cell(row, *column) > 0.0))
+        .collect();
+
+    if rows.len() < 2 || columns.len() < 2 {
+        return None;
+    }
+
+    let row_totals: Vec<f64> = rows
+        .iter()
+        .map(|row| columns.iter().map(|column| cell(row, *column)).sum())
+        .collect();
+    let column_totals: Vec<f64> = columns
+        .iter()
+        .map(|column| rows.iter().map(|row| cell(row, *column)).sum())
+        .collect();
+    let n: f64 = row_totals.iter().sum();
+
+    let mut statistic = 0.0;
+
+    for (row, row_total) in rows.iter().zip(&row_totals) {
+        for (column, column_total) in columns.iter().zip(&column_totals) {
+            let expected = row_total * column_total / n;
+            statistic += (cell(row, *column) - expected).powi(2) / expected;
+        }
+    }
+
+    let df = (rows.len() - 1) * (columns.len() - 1);
+    let smaller = rows.len().min(columns.len()) as f64;
+
+    Some(ChiSquare {
+        statistic: statistic as f32,
+        df,
+        p_value: chi_square_p_value(statistic, df as f64) as f32,
+        effect_size: (statistic / (n * (smaller - 1.0))).sqrt() as f32,
+    })
+}
+
+/// Lines up counts of the same categories from several sources into a table,
+/// with a row per source and columns in order of first appearance.
+pub fn contingency_table<T: PartialEq + Clone>(
+    rows: &[&[(T, usize)]],
+) -> (Vec<T>, Vec<Vec<usize>>) {
+    let mut categories: Vec<T> = vec![];
+
+    for row in rows {
+        for (category, _) in row.iter() {
+            if !categories.contains(category) {
+                categories.push(category.to_owned())
+            }
+        }
+    }
+
+    let table = rows
+        .iter()
+        .map(|row| {
+            categories
+                .iter()
+                .map(|category| {
+                    row.iter()
+                        .find(|(counted, _)| counted == category)
+                        .map_or(0, |(_, count)| *count)
+                })
+                .collect()
+        })
+        .collect();
+
+    (categories, table)
+}
+
+/// Wilson score interval at `level` for the rate of `successes` in `trials`.
+/// Unlike the normal approximation, it stays within 0 and 1 for small samples.
+/// `None` without any trials.
+pub fn wilson_interval(successes: usize, trials: usize, level: f32) -> Option<ConfidenceInterval> {
+    if trials == 0 {
+        return None;
+    }
+
+    let (k, n) = (successes as f64, trials as f64);
+    let z = z_critical(level as f64);
+    let p = k / n;
+
+    let centre = (p + z * z / (2.0 * n)) / (1.0 + z * z / n);
+    let margin = z / (1.0 + z * z / n) * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt();
+
+    Some(ConfidenceInterval {
+        estimate: p as f32,
+        lower: (centre - margin).max(0.0) as f32,
+        upper: (centre + margin).min(1.0) as f32,
+        level,
+    })
+}
+
+/// Two-sided p-value of `t` under Student's t distribution.
+fn t_p_value(t: f64, df: f64) -> f64 {
+    incomplete_beta(df / 2.0, 0.5, df / (df + t * t))
+}
+
+/// The `t` that leaves `1 - level` of Student's t distribution in its two tails.
+pub(crate) fn t_critical(df: f64, level: f64) -> f64 {
+    let (mut low, mut high) = (0.0, 1000.0);
+
+    // The p-value falls as t rises.
+    for _ in 0..100 {
+        let mid = (low + high) / 2.0;
+
+        if t_p_value(mid, df) > 1.0 - level {
+            low = mid
+        } else {
+            high = mid
+        }
+    }
+
+    (low + high) / 2.0
+}
+
+/// The `z` that leaves `1 - level` of the standard normal distribution in its two tails.
+pub(crate) fn z_critical(level: f64) -> f64 {
+    let (mut low, mut high) = (0.0, 100.0);
+
+    // Z squared is chi-square with one degree of freedom.
+    for _ in 0..100 {
+        let mid = (low + high) / 2.0;
+
+        if chi_square_p_value(mid * mid, 1.0) > 1.0 - level {
+            low = mid
+        } else {
+            high = mid
+        }
+    }
+
+    (low + high) / 2.0
+}
+
+/// Upper tail of the chi-square distribution.
+fn chi_square_p_value(statistic: f64, df: f64) -> f64 {
+    upper_incomplete_gamma(df / 2.0, statistic / 2.0)
+}
+
+/// Natural log of the gamma function, by the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+
+    if x < 0.5 {
+        // Reflection, as the approximation only holds for larger x.
+        return (std::f64::consts::PI / (std::f64::consts::PI * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+
+    let x = x - 1.0;
+    let t = x + 7.5;
+
+    let sum = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .skip(1)
+        .fold(COEFFICIENTS[0], |sum, (idx, coefficient)| {
+            sum + coefficient / (x + idx as f64)
+        });
+
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + sum.ln()
+}
+
+/// The regularized incomplete beta function I_x(a, b).
+fn incomplete_beta(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+
+    if x >= 1.0 {
+        return 1.0;
+    }
+
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+
+    // The continued fraction converges quickly on this side of the mean.
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Lentz's method for the continued fraction of the incomplete beta function.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+
+    if d.abs() < TINY {
+        d = TINY
+    }
+
+    d = 1.0 / d;
+    let mut fraction = d;
+
+    for m in 1..300 {
+        let m = m as f64;
+
+        for numerator in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + numerator * d;
+            c = 1.0 + numerator / c;
+
+            if d.abs() < TINY {
+                d = TINY
+            }
+
+            if c.abs() < TINY {
+                c = TINY
+            }
+
+            d = 1.0 / d;
+            fraction *= d * c;
+        }
+
+        if (d * c - 1.0).abs() < 1e-12 {
+            break;
+        }
+    }
+
+    fraction
+}
+
+/// The regularized upper incomplete gamma function Q(a, x).
+fn upper_incomplete_gamma(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+
+    let front = (a * x.ln() - x - ln_gamma(a)).exp();
+
+    if x < a + 1.0 {
+        // Series for the lower function.
+        let mut term = 1.0 / a;
+        let mut sum = term;
+
+        for n in 1..500 {
+            term *= x / (a + n as f64);
+            sum += term;
+
+            if term.abs() < sum.abs() * 1e-14 {
+                break;
+            }
+        }
+
+        1.0 - front * sum
+    } else {
+        // Lentz's method for the continued fraction.
+        const TINY: f64 = 1e-300;
+
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut fraction = d;
+
+        for n in 1..500 {
+            let n = n as f64;
+            let numerator = -n * (n - a);
+
+            b += 2.0;
+            d = numerator * d + b;
+            c = b + numerator / c;
+
+            if d.abs() < TINY {
+                d = TINY
+            }
+
+            if c.abs() < TINY {
+                c = TINY
+            }
+
+            d = 1.0 / d;
+            fraction *= d * c;
+
+            if (d * c - 1.0).abs() < 1e-14 {
+                break;
+            }
+        }
+
+        front * fraction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::hypothesis::*;
+
+    fn close(a: f32, b: f32, tolerance: f32) -> bool {
+        (a - b).abs() < tolerance
+    }
+
+    #[test]
+    fn distributions() {
+        // Reference values from standard tables.
+        assert!((ln_gamma(5.0) - 24.0_f64.ln()).abs() < 1e-10);
+        assert!((ln_gamma(0.5) - std::f64::consts::PI.sqrt().ln()).abs() < 1e-10);
+        assert!((t_p_value(2.228, 10.0) - 0.05).abs() < 1e-3);
+        assert!((t_critical(10.0, 0.95) - 2.228).abs() < 1e-3);
+        assert!((z_critical(0.95) - 1.96).abs() < 1e-3);
+        assert!((z_critical(0.99) - 2.576).abs() < 1e-3);
+        assert!((chi_square_p_value(3.841, 1.0) - 0.05).abs() < 1e-3);
+        assert!((chi_square_p_value(11.07, 5.0) - 0.05).abs() < 1e-3);
+        assert!((chi_square_p_value(2.0, 30.0) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn wilson() {
+        let interval = wilson_interval(8, 10, 0.95).unwrap();
+
+        assert!(interval.estimate == 0.8);
+        assert!(close(interval.lower, 0.4902, 1e-3));
+        assert!(close(interval.upper, 0.9433, 1e-3));
+
+        let none = wilson_interval(0, 10, 0.95).unwrap();
+
+        assert!(none.lower == 0.0 && none.upper > 0.0);
+        assert!(wilson_interval(0, 0, 0.95).is_none());
+    }
+
+    #[test]
+    fn welch() {
+        let a = Summary::new(&[19.0, 22.0, 16.0, 29.0, 24.0]).unwrap();
+        let b = Summary::new(&[20.0, 11.0, 17.0, 12.0]).unwrap();
+
+        let test = welch_t_test(&a, &b).unwrap();
+
+        assert!(close(test.t, 2.2831, 1e-3));
+        assert!(close(test.df, 6.9288, 1e-3));
+        assert!(close(test.p_value, 0.0568, 1e-3));
+        assert!(test.difference.lower < 0.0 && test.difference.upper > test.difference.estimate);
+
+        let flat = Summary::new(&[3.0, 3.0]).unwrap();
+
+        assert!(welch_t_test(&flat, &flat).is_none());
+        assert!(welch_t_test(&a, &Summary::new(&[1.0]).unwrap()).is_none());
+    }
+
+    #[test]
+    fn chi_square() {
+        let fair = chi_square_goodness_of_fit(&[16, 18, 16, 14, 12, 12], &[1.0; 6]).unwrap();
+
+        assert!(close(fair.statistic, 2.0, 1e-4));
+        assert!(fair.df == 5);
+        assert!(close(fair.p_value, 0.8491, 1e-3));
+
+        assert!(chi_square_goodness_of_fit(&[1, 2], &[1.0, 0.0]).is_none());
+
+        let table = chi_square_independence(&[vec![10, 20, 0], vec![30, 40, 0]]).unwrap();
+
+        assert!(table.df == 1);
+        assert!(close(table.statistic, 0.7937, 1e-3));
+
+        let (categories, table) =
+            contingency_table(&[&[("a", 1), ("b", 2)][..], &[("c", 3), ("a", 4)][..]]);
+
+        assert!(categories == vec!["a", "b", "c"]);
+        assert!(table == vec![vec![1, 2, 0], vec![4, 0, 3]]);
+    }
+}
@@ -0,0 +1,273 @@
+use crate::{
+    Flags, LogFile, Team,
+    stats::{ChiSquare, Summary, TTest, chi_square_independence, welch_t_test},
+};
+use strum::{EnumIter, IntoEnumIterator};
+
+/// The last score before a drive, since the team's previous drive began.
+#[derive(Debug, Clone, PartialEq, EnumIter)]
+pub enum Situation {
+    AfterOwnScore,
+    AfterOpponentScore,
+    /// Nobody scored.
+    Baseline,
+}
+
+/// A team's drives in one situation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MomentumBucket {
+    pub situation: Situation,
+    pub plays_per_drive: Vec<usize>,
+    /// Every delta of every drive, in order.
+    pub deltas: Vec<i8>,
+    /// Only for games where actions are kept.
+    pub runs: usize,
+    pub passes: usize,
+}
+
+impl MomentumBucket {
+    fn new(situation: Situation) -> Self {
+        Self {
+            situation,
+            plays_per_drive: vec![],
+            deltas: vec![],
+            runs: 0,
+            passes: 0,
+        }
+    }
+
+    pub fn plays_summary(&self) -> Option<Summary> {
+        Summary::from_counts(
+            &self
+                .plays_per_drive
+                .iter()
+                .map(|plays| *plays as u32)
+                .collect::<Vec<u32>>(),
+        )
+    }
+
+    pub fn delta_summary(&self) -> Option<Summary> {
+        Summary::from_counts(&self.deltas)
+    }
+
+    /// Fraction of known play calls that were runs.
+    pub fn run_share(&self) -> Option<f32> {
+        match self.runs + self.passes {
+            0 => None,
+            total => Some(self.runs as f32 / total as f32),
+        }
+    }
+}
+
+/// Whether a team plays differently straight after a score.
+///
+/// Games flagged `Flags::IgnoreScore` are left out, since the situation of
+/// their drives is unknown.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Momentum {
+    pub team: Team,
+    /// One per `Situation`, in order.
+    pub buckets: Vec<MomentumBucket>,
+    /// Plays per drive after scoring against baseline.
+    pub own_score_plays: Option<TTest>,
+    /// Deltas after scoring against baseline.
+    pub own_score_delta: Option<TTest>,
+    /// Plays per drive after conceding against baseline.
+    pub opponent_score_plays: Option<TTest>,
+    /// Deltas after conceding against baseline.
+    pub opponent_score_delta: Option<TTest>,
+    /// Whether the run/pass mix depends on the situation.
+    pub run_pass_mix: Option<ChiSquare>,
+}
+
+impl Momentum {
+    pub fn new(log: &LogFile, team: Team) -> Self {
+        let mut buckets: Vec<MomentumBucket> = Situation::iter().map(MomentumBucket::new).collect();
+
+        for game in &log.0 {
+            if game.flags.contains(&Flags::IgnoreScore)
+                || !game.teams().unwrap_or_default().contains(&team)
+            {
+                continue;
+            }
+
+            let scores: Vec<_> = game
+                .team_scores()
+                .into_iter()
+                .filter(|score| !score.points.is_pat())
+                .collect();
+            let mut deltas = game.play_deltas(team.to_owned()).into_iter();
+            let mut since = 0;
+
+            for drive in game.team_drives(team.to_owned()) {
+                let situation = match scores
+                    .iter()
+                    .rfind(|score| (since..drive.start).contains(&score.event_index))
+                {
+                    Some(score) if score.team == team => Situation::AfterOwnScore,
+                    Some(_) => Situation::AfterOpponentScore,
+                    None => Situation::Baseline,
+                };
+                since = drive.start;
+
+                let bucket = buckets
+                    .iter_mut()
+                    .find(|bucket| bucket.situation == situation)
+                    .unwrap();
+                let plays = drive.plays();
+
+                bucket.plays_per_drive.push(plays.len());
+
+                // `play_deltas` runs over the same drives, a play at a time.
+                bucket.deltas.extend(
+                    deltas
+                        .by_ref()
+                        .take(plays.len())
+                        .filter_map(|(_, delta)| delta),
+                );
+
+                if !game.flags.contains(&Flags::IgnoreActions) {
+                    for play in plays.iter().filter(|play| !play.action.is_unknown()) {
+                        if play.action.is_run() {
+                            bucket.runs += 1
+                        } else {
+                            bucket.passes += 1
+                        }
+                    }
+                }
+            }
+        }
+
+        let summaries = |summary: fn(&MomentumBucket) -> Option<Summary>| {
+            buckets
+                .iter()
+                .map(summary)
+                .collect::<Vec<Option<Summary>>>()
+        };
+        let test =
+            |a: &Option<Summary>, b: &Option<Summary>| welch_t_test(a.as_ref()?, b.as_ref()?);
+
+        let plays = summaries(MomentumBucket::plays_summary);
+        let deltas = summaries(MomentumBucket::delta_summary);
+
+        let table: Vec<Vec<usize>> = buckets
+            .iter()
+            .map(|bucket| vec![bucket.runs, bucket.passes])
+            .collect();
+
+        Self {
+            team,
+            own_score_plays: test(&plays[0], &plays[2]),
+            own_score_delta: test(&deltas[0], &deltas[2]),
+            opponent_score_plays: test(&plays[1], &plays[2]),
+            opponent_score_delta: test(&deltas[1], &deltas[2]),
+            run_pass_mix: chi_square_independence(&table),
+            buckets,
+        }
+    }
+
+    pub fn bucket(&self, situation: &Situation) -> &MomentumBucket {
+        self.buckets
+            .iter()
+            .find(|bucket| bucket.situation == *situation)
+            .unwrap()
+    }
+
+    /// Whether the team gains significantly less after scoring, at `alpha`, e.g. `0.05`.
+    /// `None` without enough drives to tell.
+    pub fn slows_after_score(&self, alpha: f32) -> Option<bool> {
+        let test = self.own_score_delta.as_ref()?;
+
+        Some(test.p_value < alpha && test.difference.estimate < 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{gamelog, play};
+    use crate::{stats::*, *};
+
+    #[test]
+    fn situations() {
+        let log = LogFile(vec![Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events: vec![
+                Event::Kickoff(Team::Nebraska, None, None),
+                play(Action::SlotOut, Down::Second, TerrainState::Yards(4)),
+                play(Action::PowerZero, Down::First, TerrainState::Yards(10)),
+                Event::Score(ScorePoints::Touchdown, None),
+                Event::Score(ScorePoints::PatFieldGoal, None),
+                Event::Kickoff(Team::Iowa, None, None),
+                play(Action::Curls, Down::Second, TerrainState::Yards(10)),
+                Event::Punt {
+                    offence: Team::Nebraska,
+                    distance: None,
+                    return_yards: None,
+                    position: None,
+                },
+                play(Action::HalfbackSweep, Down::Second, TerrainState::Yards(8)),
+                play(Action::HalfbackSlam, Down::Third, TerrainState::Yards(9)),
+                Event::Turnover(Team::Iowa, Some(TurnoverKind::Fumble)),
+                Event::Score(ScorePoints::FieldGoal, None),
+                Event::Kickoff(Team::Nebraska, None, None),
+                play(Action::Mesh, Down::Second, TerrainState::Yards(3)),
+                Event::Turnover(Team::Iowa, Some(TurnoverKind::Downs)),
+                Event::Punt {
+                    offence: Team::Nebraska,
+                    distance: None,
+                    return_yards: None,
+                    position: None,
+                },
+                play(Action::SlotOut, Down::First, TerrainState::Yards(10)),
+            ],
+        }]);
+
+        let momentum = Momentum::new(&log, Team::Nebraska);
+        let own = momentum.bucket(&Situation::AfterOwnScore);
+        let opponent = momentum.bucket(&Situation::AfterOpponentScore);
+        let baseline = momentum.bucket(&Situation::Baseline);
+
+        // The punt back follows Nebraska's touchdown, not Iowa's drive.
+        assert!(own.plays_per_drive == vec![2]);
+        assert!(own.deltas == vec![2, -1] && own.runs == 2 && own.passes == 0);
+        assert!(opponent.plays_per_drive == vec![1] && opponent.deltas == vec![7]);
+        assert!(baseline.plays_per_drive == vec![2, 1]);
+        assert!(baseline.deltas == vec![6, 4, 10]);
+        assert!(baseline.runs == 1 && baseline.passes == 2);
+
+        // One drive is not enough to compare, but two plays are.
+        assert!(momentum.own_score_plays.is_none());
+        assert!(momentum.slows_after_score(0.05) == Some(false));
+        assert!(momentum.slows_after_score(0.2) == Some(true));
+        assert!(momentum.opponent_score_delta.is_none());
+        assert!(momentum.run_pass_mix.is_some());
+
+        let iowa = Momentum::new(&log, Team::Iowa);
+
+        assert!(iowa.bucket(&Situation::AfterOpponentScore).plays_per_drive == vec![1]);
+        assert!(iowa.bucket(&Situation::Baseline).plays_per_drive == vec![0]);
+    }
+
+    #[test]
+    fn ignore_score() {
+        let log = gamelog();
+
+        let momentum = Momentum::new(&log, Team::Nebraska);
+        let drives: usize = log
+            .0
+            .iter()
+            .filter(|game| !game.flags.contains(&Flags::IgnoreScore))
+            .map(|game| game.team_drives(Team::Nebraska).len())
+            .sum();
+
+        assert!(
+            momentum
+                .buckets
+                .iter()
+                .map(|bucket| bucket.plays_per_drive.len())
+                .sum::<usize>()
+                == drives
+        );
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{Action, Down, Flags, LogFile, Team, stats::DOWNS};
+use std::{cmp::Reverse, fmt};
+
+/// A run of consecutive play calls a team repeated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    pub actions: Vec<Action>,
+    /// Times the run was called.
+    pub support: usize,
+    /// Times the run started on each down, where the down faced was known.
+    pub after_downs: Vec<(Down, usize)>,
+}
+
+impl Pattern {
+    /// Returns `true` if the run started on `down` more than once.
+    pub fn recurs_after(&self, down: &Down) -> bool {
+        self.after_downs
+            .iter()
+            .any(|(faced, count)| faced == down && *count > 1)
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let actions: Vec<String> = self
+            .actions
+            .iter()
+            .map(|action| format!("{:?}", action))
+            .collect();
+
+        write!(f, "{} x{}", actions.join(" > "), self.support)?;
+
+        let downs: Vec<String> = self
+            .after_downs
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(down, count)| format!("{:?} {}", down, count))
+            .collect();
+
+        if !downs.is_empty() {
+            write!(f, " ({})", downs.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Every run of `length` consecutive plays `team` called more than once over
+/// `log`, most frequent first.
+///
+/// Runs stay within a drive, and are left out if any of their actions is
+/// unknown. Games flagged `Flags::IgnoreActions` are left out.
+pub fn play_patterns(log: &LogFile, team: Team, length: usize) -> Vec<Pattern> {
+    let mut patterns: Vec<Pattern> = vec![];
+
+    if length == 0 {
+        return patterns;
+    }
+
+    for game in &log.0 {
+        if game.flags.contains(&Flags::IgnoreActions) {
+            continue;
+        }
+
+        for drive in game.faced_plays(team.to_owned()) {
+            for run in drive.windows(length) {
+                if run.iter().any(|(_, play)| play.action.is_unknown()) {
+                    continue;
+                }
+
+                let actions: Vec<Action> =
+                    run.iter().map(|(_, play)| play.action.to_owned()).collect();
+
+                let pattern = match patterns
+                    .iter_mut()
+                    .find(|pattern| pattern.actions == actions)
+                {
+                    Some(pattern) => pattern,
+                    None => {
+                        patterns.push(Pattern {
+                            actions,
+                            support: 0,
+                            after_downs: DOWNS.into_iter().map(|down| (down, 0)).collect(),
+                        });
+
+                        patterns.last_mut().unwrap()
+                    }
+                };
+
+                pattern.support += 1;
+
+                if let Some(down) = &run[0].0.down
+                    && let Some(total) = pattern
+                        .after_downs
+                        .iter_mut()
+                        .find(|(faced, _)| faced == down)
+                {
+                    total.1 += 1
+                }
+            }
+        }
+    }
+
+    patterns.retain(|pattern| pattern.support > 1);
+
+    // Stable, so ties stay in order of first use.
+    patterns.sort_by_key(|pattern| Reverse(pattern.support));
+
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::play;
+    use crate::*;
+
+    #[test]
+    fn play_patterns() {
+        let log = LogFile(vec![Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events: vec![
+                Event::Kickoff(Team::Nebraska, None, None),
+                play(Action::SlotOut, Down::Second, None),
+                play(Action::Curls, Down::Third, None),
+                play(Action::Mesh, Down::First, None),
+                play(Action::SlotOut, Down::Second, None),
+                play(Action::Curls, Down::Third, None),
+                Event::Punt {
+                    offence: Team::Iowa,
+                    distance: None,
+                    return_yards: None,
+                    position: None,
+                },
+                play(Action::Mesh, Down::Second, None),
+                Event::Turnover(Team::Nebraska, None),
+                // Runs do not span drives.
+                play(Action::Mesh, Down::Second, None),
+                play(Action::SlotOut, Down::Third, None),
+                play(Action::Curls, Down::Fourth, None),
+                play(Action::Unknown, Down::First, None),
+                play(Action::Mesh, Down::Second, None),
+            ],
+        }]);
+
+        let pairs = super::play_patterns(&log, Team::Nebraska, 2);
+
+        assert!(pairs.len() == 2);
+        assert!(pairs[0].actions == vec![Action::SlotOut, Action::Curls]);
+        assert!(pairs[0].support == 3);
+        assert!(pairs[0].after_downs[0] == (Down::First, 2));
+        assert!(pairs[0].after_downs[1] == (Down::Second, 1));
+        assert!(pairs[0].recurs_after(&Down::First));
+        assert!(!pairs[0].recurs_after(&Down::Second));
+        assert!(pairs[1].actions == vec![Action::Mesh, Action::SlotOut]);
+        assert!(pairs[1].support == 2);
+
+        let triples = super::play_patterns(&log, Team::Nebraska, 3);
+
+        assert!(triples.len() == 1 && triples[0].support == 2);
+        assert!(triples[0].actions == vec![Action::Mesh, Action::SlotOut, Action::Curls]);
+
+        assert!(super::play_patterns(&log, Team::Nebraska, 4).is_empty());
+        assert!(super::play_patterns(&log, Team::Nebraska, 0).is_empty());
+        assert!(super::play_patterns(&log, Team::Iowa, 2).is_empty());
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{Action, Flags, Key, LogFile, Playset, Team, stats::is_success};
+use std::{cmp::Reverse, fmt};
+use strum::IntoEnumIterator;
+
+/// How often, and how well, a team picked one choice, like an action or a playset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tendency<T> {
+    pub choice: T,
+    pub count: usize,
+    /// Fraction of the team's calls that made any of these choices.
+    pub share: f32,
+    /// Mean change in yards-to-go, over the calls it could be measured for.
+    pub mean_gain: Option<f32>,
+    /// Fraction of judged calls that were a success, see `stats::is_success`.
+    pub success_rate: Option<f32>,
+    /// 1 for the most frequent, with ties sharing a rank.
+    pub rank: usize,
+}
+
+/// How often, and how well, a team called one action.
+pub type PlayCall = Tendency<Action>;
+
+impl<T: fmt::Debug> fmt::Display for Tendency<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} {:?}: {} calls ({:.1}%)",
+            self.rank,
+            self.choice,
+            self.count,
+            self.share * 100.0
+        )?;
+
+        if let (Some(gain), Some(success)) = (self.mean_gain, self.success_rate) {
+            write!(f, ", gain {:.2}, success {:.1}%", gain, success * 100.0)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `team`'s calls of every known action over `log`, most frequent first.
+/// Games flagged `Flags::IgnoreActions` are left out.
+pub fn play_calls(log: &LogFile, team: Team) -> Vec<PlayCall> {
+    tendencies(
+        log,
+        team,
+        Action::iter().filter(|action| !action.is_unknown()),
+        |action| Some(action.to_owned()),
+    )
+}
+
+/// `team`'s calls from every playset over `log`, most frequent first.
+/// Actions outside of a playset, like `Action::HailMary`, are left out.
+pub fn playset_calls(log: &LogFile, team: Team) -> Vec<Tendency<Playset>> {
+    tendencies(log, team, Playset::iter(), Action::playset)
+}
+
+/// `team`'s calls on every key over `log`, most frequent first.
+/// Actions not on a key, like `Action::HailMary`, are left out.
+pub fn key_calls(log: &LogFile, team: Team) -> Vec<Tendency<Key>> {
+    tendencies(log, team, Key::iter(), Action::key)
+}
+
+/// The choice with the greatest mean gain.
+pub fn most_effective_play<T>(calls: &[Tendency<T>]) -> Option<&Tendency<T>> {
+    calls.iter().filter(|call| call.mean_gain.is_some()).fold(
+        None,
+        |best: Option<&Tendency<T>>, call| match best {
+            Some(best) if best.mean_gain >= call.mean_gain => Some(best),
+            _ => Some(call),
+        },
+    )
+}
+
+/// Groups `team`'s plays over `log` by `choice_of` their action,
+/// keeping every one of `choices`, even those never picked.
+fn tendencies<T: PartialEq>(
+    log: &LogFile,
+    team: Team,
+    choices: impl Iterator<Item = T>,
+    choice_of: impl Fn(&Action) -> Option<T>,
+) -> Vec<Tendency<T>> {
+    // Each choice's deltas, and whether each judged call was a success.
+    let mut deltas: Vec<(T, Vec<Option<i8>>, Vec<bool>)> =
+        choices.map(|choice| (choice, vec![], vec![])).collect();
+
+    for game in &log.0 {
+        if game.flags.contains(&Flags::IgnoreActions) {
+            continue;
+        }
+
+        for (faced, play, delta) in game.faced_deltas(team.to_owned()) {
+            let Some(choice) = choice_of(&play.action) else {
+                continue;
+            };
+
+            if let Some((_, calls, judged)) =
+                deltas.iter_mut().find(|(picked, _, _)| *picked == choice)
+            {
+                calls.push(delta);
+                judged.extend(delta.and_then(|delta| is_success(&faced, delta)));
+            }
+        }
+    }
+
+    let total: usize = deltas.iter().map(|(_, calls, _)| calls.len()).sum();
+
+    let mut calls: Vec<Tendency<T>> = deltas
+        .into_iter()
+        .map(|(choice, calls, judged)| {
+            let measured: Vec<i8> = calls.iter().flatten().copied().collect();
+
+            let mean_gain = match measured.len() {
+                0 => None,
+                n => Some(measured.iter().map(|delta| *delta as f32).sum::<f32>() / n as f32),
+            };
+            let success_rate = match judged.len() {
+                0 => None,
+                n => Some(judged.iter().filter(|success| **success).count() as f32 / n as f32),
+            };
+
+            Tendency {
+                choice,
+                count: calls.len(),
+                share: if total == 0 {
+                    0.0
+                } else {
+                    calls.len() as f32 / total as f32
+                },
+                mean_gain,
+                success_rate,
+                rank: 0,
+            }
+        })
+        .collect();
+
+    // Stable, so ties stay in declaration order.
+    calls.sort_by_key(|call| Reverse(call.count));
+
+    let counts: Vec<usize> = calls.iter().map(|call| call.count).collect();
+
+    for call in calls.iter_mut() {
+        call.rank = counts.iter().filter(|count| **count > call.count).count() + 1
+    }
+
+    calls
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::*;
+    use crate::testing::gamelog;
+
+    #[test]
+    fn play_calls() {
+        let log = gamelog();
+
+        let calls = super::play_calls(&log, Team::Nebraska);
+        let stats = TeamStats::from_log(&log, Team::Nebraska);
+
+        // Every known action, whether called or not.
+        assert!(calls.len() == 16);
+        assert!(calls.iter().all(|call| !call.choice.is_unknown()));
+
+        assert!(calls[0].rank == 1 && calls[1].rank == 1);
+        assert!(calls[0].choice == Action::PlayActionComebacks);
+        assert!(calls[0].count == 13);
+        assert!(Some(calls[0].choice.to_owned()) == stats.most_common_play);
+
+        let total: usize = calls.iter().map(|call| call.count).sum();
+
+        assert!(total == stats.play_counts.iter().map(|(_, count)| count).sum());
+        assert!((calls.iter().map(|call| call.share).sum::<f32>() - 1.0).abs() < 1e-4);
+
+        let unused = calls.last().unwrap();
+
+        assert!(unused.count == 0 && unused.mean_gain.is_none() && unused.rank == 15);
+
+        let best = most_effective_play(&calls).unwrap();
+
+        assert!(calls.iter().all(|call| call.mean_gain <= best.mean_gain));
+    }
+
+    #[test]
+    fn ignore_actions() {
+        let mut log = gamelog();
+
+        log.0
+            .iter_mut()
+            .for_each(|game| game.flags.push(Flags::IgnoreActions));
+
+        let calls = super::play_calls(&log, Team::Nebraska);
+
+        assert!(
+            calls
+                .iter()
+                .all(|call| call.count == 0 && call.share == 0.0)
+        );
+        assert!(most_effective_play(&calls).is_none());
+    }
+
+    #[test]
+    fn configurations() {
+        let log = gamelog();
+
+        let stats = TeamStats::from_log(&log, Team::Nebraska);
+        let plays = super::play_calls(&log, Team::Nebraska);
+        let playsets = playset_calls(&log, Team::Nebraska);
+        let keys = key_calls(&log, Team::Nebraska);
+
+        assert!(playsets.len() == 5 && keys.len() == 3);
+
+        // Every action but the Hail Mary has a playset and a key.
+        let hail_marys = plays
+            .iter()
+            .find(|call| call.choice == Action::HailMary)
+            .unwrap()
+            .count;
+        let total: usize = plays.iter().map(|call| call.count).sum();
+
+        assert!(playsets.iter().map(|call| call.count).sum::<usize>() == total - hail_marys);
+        assert!(keys.iter().map(|call| call.count).sum::<usize>() == total - hail_marys);
+
+        // Each playset is the sum of its actions.
+        for playset in &playsets {
+            let count: usize = plays
+                .iter()
+                .filter(|call| call.choice.playset() == Some(playset.choice.to_owned()))
+                .map(|call| call.count)
+                .sum();
+
+            assert!(playset.count == count);
+        }
+
+        assert!(Some(keys[0].choice.to_owned()) == stats.most_common_key);
+        assert!(Some(keys[2].choice.to_owned()) == stats.least_common_key);
+        assert!(Some(playsets[0].choice.to_owned()) == stats.most_common_playset);
+    }
+}
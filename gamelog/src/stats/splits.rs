@@ -0,0 +1,242 @@
+use crate::{
+    Action, DistanceToGo, Down, DownAndDistance, Flags, LogFile, Team, TerrainState,
+    stats::{add_one, extremes},
+};
+use std::fmt;
+use strum::{EnumIter, IntoEnumIterator};
+
+/// The down and distance a play was called on.
+#[derive(Debug, Clone, PartialEq, EnumIter)]
+pub enum Split {
+    /// First down, outside of goal-to-go.
+    First,
+    SecondLong,
+    SecondMedium,
+    SecondShort,
+    ThirdLong,
+    ThirdMedium,
+    ThirdShort,
+    /// Fourth down, outside of goal-to-go.
+    Fourth,
+    /// Any down at the `GoalLine` or `Inches` from it.
+    GoalToGo,
+}
+
+impl Split {
+    /// Returns `None` where the down, or the distance on second and third, is unknown.
+    pub fn from_faced(faced: &DownAndDistance) -> Option<Self> {
+        if let Some(TerrainState::GoalLine | TerrainState::Inches) = faced.terrain {
+            return Some(Self::GoalToGo);
+        }
+
+        Some(match (faced.down.as_ref()?, faced.distance()) {
+            (Down::First, _) => Self::First,
+            (Down::Second, Some(DistanceToGo::Long)) => Self::SecondLong,
+            (Down::Second, Some(DistanceToGo::Medium)) => Self::SecondMedium,
+            (Down::Second, Some(DistanceToGo::Short)) => Self::SecondShort,
+            (Down::Third, Some(DistanceToGo::Long)) => Self::ThirdLong,
+            (Down::Third, Some(DistanceToGo::Medium)) => Self::ThirdMedium,
+            (Down::Third, Some(DistanceToGo::Short)) => Self::ThirdShort,
+            (Down::Fourth, _) => Self::Fourth,
+            _ => return None,
+        })
+    }
+}
+
+/// How a team played in one `Split`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitStats {
+    pub split: Split,
+    pub plays: usize,
+    /// Times each known action was called, in order of first use.
+    /// Games flagged `Flags::IgnoreActions` are left out.
+    pub play_counts: Vec<(Action, usize)>,
+    pub most_common_play: Option<Action>,
+    /// Deltas of the plays they could be measured for.
+    pub deltas: Vec<i8>,
+    /// Plays that left the offence on a new first down.
+    pub conversions: usize,
+    /// Plays the resulting down was noted for.
+    pub attempts: usize,
+}
+
+impl SplitStats {
+    fn new(split: Split) -> Self {
+        Self {
+            split,
+            plays: 0,
+            play_counts: vec![],
+            most_common_play: None,
+            deltas: vec![],
+            conversions: 0,
+            attempts: 0,
+        }
+    }
+
+    /// Fraction of known play calls that were runs.
+    pub fn run_share(&self) -> Option<f32> {
+        let (runs, total) =
+            self.play_counts
+                .iter()
+                .fold((0, 0), |(runs, total), (action, count)| {
+                    (
+                        runs + if action.is_run() { *count } else { 0 },
+                        total + count,
+                    )
+                });
+
+        match total {
+            0 => None,
+            total => Some(runs as f32 / total as f32),
+        }
+    }
+
+    pub fn avg_delta(&self) -> Option<f32> {
+        match self.deltas.len() {
+            0 => None,
+            n => Some(self.deltas.iter().map(|delta| *delta as f32).sum::<f32>() / n as f32),
+        }
+    }
+
+    pub fn conversion_rate(&self) -> Option<f32> {
+        match self.attempts {
+            0 => None,
+            attempts => Some(self.conversions as f32 / attempts as f32),
+        }
+    }
+}
+
+impl fmt::Display for SplitStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}: {} plays", self.split, self.plays)?;
+
+        if let Some(play) = &self.most_common_play {
+            write!(f, ", mostly {:?}", play)?;
+        }
+
+        if let Some(share) = self.run_share() {
+            write!(f, ", run {:.1}%", share * 100.0)?;
+        }
+
+        if let Some(delta) = self.avg_delta() {
+            write!(f, ", delta {:.2}", delta)?;
+        }
+
+        if let Some(rate) = self.conversion_rate() {
+            write!(
+                f,
+                ", converted {}/{} ({:.1}%)",
+                self.conversions,
+                self.attempts,
+                rate * 100.0
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `team`'s play in every `Split` over `log`, in order.
+/// Teams are left out of the games they are ignored in.
+pub fn situational_splits(log: &LogFile, team: Team) -> Vec<SplitStats> {
+    let mut splits: Vec<SplitStats> = Split::iter().map(SplitStats::new).collect();
+
+    for game in &log.0 {
+        if !game.teams().unwrap_or_default().contains(&team) {
+            continue;
+        }
+
+        for (faced, play, delta) in game.faced_deltas(team.to_owned()) {
+            let Some(split) = Split::from_faced(&faced) else {
+                continue;
+            };
+            let stats = splits
+                .iter_mut()
+                .find(|stats| stats.split == split)
+                .unwrap();
+
+            stats.plays += 1;
+
+            if !game.flags.contains(&Flags::IgnoreActions) && !play.action.is_unknown() {
+                add_one(&mut stats.play_counts, play.action)
+            }
+
+            stats.deltas.extend(delta);
+
+            if let Some(down) = play.down {
+                stats.attempts += 1;
+
+                if down == Down::First {
+                    stats.conversions += 1
+                }
+            }
+        }
+    }
+
+    for stats in splits.iter_mut() {
+        (stats.most_common_play, _) = extremes(&stats.play_counts);
+    }
+
+    splits
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::{gamelog, play};
+    use crate::{stats::*, *};
+
+    #[test]
+    fn situational_splits() {
+        let log = LogFile(vec![Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events: vec![
+                Event::Kickoff(Team::Nebraska, None, None),
+                play(Action::SlotOut, Down::Second, TerrainState::Yards(2)),
+                play(Action::PowerZero, Down::First, TerrainState::Yards(10)),
+                play(Action::PowerZero, Down::Second, TerrainState::Yards(9)),
+                play(Action::Curls, Down::Third, TerrainState::Yards(9)),
+                play(Action::Mesh, Down::First, TerrainState::GoalLine),
+                play(Action::HalfbackSlam, Down::Second, TerrainState::GoalLine),
+                Event::Score(ScorePoints::Touchdown, None),
+                Event::Kickoff(Team::Iowa, None, None),
+            ],
+        }]);
+
+        let splits = super::situational_splits(&log, Team::Nebraska);
+        let split = |split: Split| splits.iter().find(|stats| stats.split == split).unwrap();
+
+        let first = split(Split::First);
+
+        assert!(first.plays == 2 && first.conversions == 0 && first.attempts == 2);
+        assert!(first.deltas == vec![8, 1]);
+        assert!(first.most_common_play == Some(Action::SlotOut));
+        assert!(first.run_share() == Some(0.5));
+
+        assert!(split(Split::SecondShort).conversion_rate() == Some(1.0));
+        assert!(split(Split::SecondLong).plays == 1);
+        assert!(split(Split::ThirdLong).conversion_rate() == Some(1.0));
+        assert!(split(Split::GoalToGo).plays == 1);
+        assert!(split(Split::Fourth).plays == 0 && split(Split::Fourth).avg_delta().is_none());
+    }
+
+    #[test]
+    fn every_play() {
+        let log = gamelog();
+
+        let splits = super::situational_splits(&log, Team::Nebraska);
+        let plays: usize = log
+            .0
+            .iter()
+            .map(|game| game.team_plays(Team::Nebraska))
+            .sum();
+        let split: usize = splits.iter().map(|stats| stats.plays).sum();
+
+        assert!(split > 0 && split <= plays);
+        assert!(
+            splits
+                .iter()
+                .all(|stats| stats.conversions <= stats.attempts)
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::stats::{ConfidenceInterval, hypothesis};
+use std::fmt;
+
+/// Descriptive statistics of a set of samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub n: usize,
+    pub mean: f32,
+    pub median: f32,
+    /// The most frequent sample, the smallest of them on a tie.
+    pub mode: f32,
+    pub min: f32,
+    pub max: f32,
+    /// First quartile, interpolated between the closest samples.
+    pub q1: f32,
+    /// Third quartile, interpolated between the closest samples.
+    pub q3: f32,
+    /// Sample variance, `None` with fewer than two samples.
+    pub variance: Option<f32>,
+}
+
+impl Summary {
+    /// Summarises `samples`, `None` if there are none.
+    pub fn new(samples: &[f32]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let n = sorted.len();
+        let mean = sorted.iter().sum::<f32>() / n as f32;
+
+        let variance = if n > 1 {
+            Some(sorted.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / (n - 1) as f32)
+        } else {
+            None
+        };
+
+        // Equal samples are next to each other once sorted.
+        let mut mode = (sorted[0], 0);
+        let mut run = (sorted[0], 0);
+
+        for sample in &sorted {
+            if *sample == run.0 {
+                run.1 += 1
+            } else {
+                run = (*sample, 1)
+            }
+
+            if run.1 > mode.1 {
+                mode = run
+            }
+        }
+
+        Some(Self {
+            n,
+            mean,
+            median: quantile(&sorted, 0.5),
+            mode: mode.0,
+            min: sorted[0],
+            max: sorted[n - 1],
+            q1: quantile(&sorted, 0.25),
+            q3: quantile(&sorted, 0.75),
+            variance,
+        })
+    }
+
+    /// Summarises integer samples, like yards.
+    pub fn from_counts<T: Copy + Into<f64>>(samples: &[T]) -> Option<Self> {
+        Self::new(
+            &samples
+                .iter()
+                .map(|sample| (*sample).into() as f32)
+                .collect::<Vec<f32>>(),
+        )
+    }
+
+    /// Interquartile range.
+    pub fn iqr(&self) -> f32 {
+        self.q3 - self.q1
+    }
+
+    /// Sample standard deviation, `None` with fewer than two samples.
+    pub fn std_dev(&self) -> Option<f32> {
+        Some(self.variance?.sqrt())
+    }
+
+    /// Interval for the mean at `level`, e.g. `0.95`, from Student's t distribution.
+    /// `None` with fewer than two samples.
+    pub fn confidence_interval(&self, level: f32) -> Option<ConfidenceInterval> {
+        let std_dev = self.std_dev()?;
+        let critical = hypothesis::t_critical((self.n - 1) as f64, level as f64) as f32;
+        let margin = critical * std_dev / (self.n as f32).sqrt();
+
+        Some(ConfidenceInterval {
+            estimate: self.mean,
+            lower: self.mean - margin,
+            upper: self.mean + margin,
+            level,
+        })
+    }
+
+    /// Standard deviations `value` lies from the mean,
+    /// `None` if the samples do not vary.
+    pub fn z_score(&self, value: f32) -> Option<f32> {
+        let std_dev = self.std_dev()?;
+
+        if std_dev == 0.0 {
+            return None;
+        }
+
+        Some((value - self.mean) / std_dev)
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "n {}, mean {:.2}, median {:.2}, mode {:.2}, min {:.2}, max {:.2}, q1 {:.2}, q3 {:.2}, iqr {:.2}",
+            self.n,
+            self.mean,
+            self.median,
+            self.mode,
+            self.min,
+            self.max,
+            self.q1,
+            self.q3,
+            self.iqr()
+        )?;
+
+        match self.std_dev() {
+            Some(std_dev) => write!(f, ", sd {:.2}", std_dev),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The `p` quantile of non-empty `sorted`, interpolating linearly between ranks.
+fn quantile(sorted: &[f32], p: f32) -> f32 {
+    let rank = p * (sorted.len() - 1) as f32;
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[low] + (sorted[high] - sorted[low]) * (rank - low as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stats::*;
+
+    #[test]
+    fn summary() {
+        let a = Summary::new(&[7.0, 2.0, 4.0, 4.0, 9.0, 4.0, 5.0, 5.0]).unwrap();
+
+        assert!(a.n == 8);
+        assert!(a.mean == 5.0);
+        assert!(a.median == 4.5);
+        assert!(a.mode == 4.0);
+        assert!(a.min == 2.0 && a.max == 9.0);
+        assert!(a.q1 == 4.0 && a.q3 == 5.5);
+        assert!(a.iqr() == 1.5);
+        assert!((a.variance.unwrap() - 32.0 / 7.0).abs() < 1e-6);
+        assert!((a.z_score(9.0).unwrap() - 4.0 / (32.0_f32 / 7.0).sqrt()).abs() < 1e-6);
+
+        // t of 2.365 with 7 degrees of freedom.
+        let interval = a.confidence_interval(0.95).unwrap();
+
+        assert!((interval.upper - 5.0 - 2.365 * (32.0_f32 / 7.0 / 8.0).sqrt()).abs() < 1e-3);
+        assert!(interval.estimate - interval.lower == interval.upper - interval.estimate);
+
+        // Ties go to the smallest.
+        assert!(Summary::from_counts(&[3_i8, -1, 3, -1]).unwrap().mode == -1.0);
+    }
+
+    #[test]
+    fn degenerate() {
+        assert!(Summary::new(&[]).is_none());
+
+        let one = Summary::new(&[3.0]).unwrap();
+
+        assert!(one.median == 3.0 && one.q1 == 3.0 && one.q3 == 3.0);
+        assert!(one.variance.is_none() && one.z_score(3.0).is_none());
+        assert!(one.confidence_interval(0.95).is_none());
+        assert!(Summary::new(&[2.0, 2.0]).unwrap().z_score(2.0).is_none());
+    }
+}
@@ -1,6 +1,7 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use strum::EnumIter;
 
-#[derive(Debug, Deserialize, Clone, Default, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
 pub enum TerrainState {
     Yards(u8),
     GoalLine,
@@ -8,3 +9,60 @@ pub enum TerrainState {
     #[default]
     Unknown,
 }
+
+/// How far the offence has to go for a first down.
+#[derive(Debug, Clone, PartialEq, EnumIter)]
+pub enum DistanceToGo {
+    /// 3 yards or less, including inches.
+    Short,
+    /// 4 to 7 yards.
+    Medium,
+    /// 8 yards or more.
+    Long,
+}
+
+impl DistanceToGo {
+    /// Returns `None` where the distance is not known, including at the goal line.
+    pub fn from_terrain(terrain: &TerrainState) -> Option<Self> {
+        match terrain {
+            TerrainState::Inches => Some(Self::Short),
+            TerrainState::Yards(0..=3) => Some(Self::Short),
+            TerrainState::Yards(4..=7) => Some(Self::Medium),
+            TerrainState::Yards(_) => Some(Self::Long),
+            TerrainState::GoalLine | TerrainState::Unknown => None,
+        }
+    }
+}
+
+/// A yard line, relative to the team on offence.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub enum YardLine {
+    /// Yards from the offence's own goal line.
+    Own(u8),
+    /// Yards from the opponent's goal line.
+    Opponent(u8),
+}
+
+impl YardLine {
+    /// Returns the yards from the offence's own goal line, from 0 to 100.
+    pub fn from_own_goal(&self) -> u8 {
+        match self {
+            Self::Own(yards) => (*yards).min(100),
+            Self::Opponent(yards) => 100 - (*yards).min(100),
+        }
+    }
+
+    /// Converts yards from the offence's own goal line back into a yard line.
+    pub fn from_yards(yards: u8) -> Self {
+        if yards > 50 {
+            Self::Opponent(100 - yards.min(100))
+        } else {
+            Self::Own(yards)
+        }
+    }
+
+    /// Returns `true` if inside the opponent's 20.
+    pub fn is_red_zone(&self) -> bool {
+        self.from_own_goal() >= 80
+    }
+}
@@ -0,0 +1,27 @@
+//! Fixtures shared by the unit tests.
+
+use crate::{Action, Down, Event, LogFile, Play, TerrainState};
+use std::path::PathBuf;
+
+/// The real games logged at the root of the repository.
+pub(crate) fn gamelog_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../gamelog.ron")
+}
+
+pub(crate) fn gamelog() -> LogFile {
+    LogFile::try_from(gamelog_path()).unwrap()
+}
+
+/// A play leaving `down` and `terrain`, without a position.
+pub(crate) fn play(
+    action: Action,
+    down: impl Into<Option<Down>>,
+    terrain: impl Into<Option<TerrainState>>,
+) -> Event {
+    Event::Play(Play {
+        action,
+        down: down.into(),
+        terrain: terrain.into(),
+        position: None,
+    })
+}
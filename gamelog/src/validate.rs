@@ -0,0 +1,387 @@
+//! Semantic checks on games that parsed, but may have been transcribed wrong.
+
+use crate::{Down, Event, FieldGoalResult, Game, LogFile, Quarter, Ruling, ScorePoints};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "error"),
+            Self::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub game_index: usize,
+    /// `None` if the diagnostic concerns the game as a whole.
+    pub event_index: Option<usize>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn error(game_index: usize, event_index: usize, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            game_index,
+            event_index: Some(event_index),
+            message,
+        }
+    }
+
+    fn warning(game_index: usize, event_index: usize, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            game_index,
+            event_index: Some(event_index),
+            message,
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.event_index {
+            Some(event) => write!(
+                f,
+                "{}: game {}, event {}: {}",
+                self.severity, self.game_index, event, self.message
+            ),
+            None => write!(
+                f,
+                "{}: game {}: {}",
+                self.severity, self.game_index, self.message
+            ),
+        }
+    }
+}
+
+/// Runs every check over every game in `log`.
+pub fn validate(log: &LogFile) -> Vec<Diagnostic> {
+    log.0
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, game)| validate_game(idx, game))
+        .collect()
+}
+
+/// Runs every check over `game`, which sits at `game_index` in its LogFile.
+pub fn validate_game(game_index: usize, game: &Game) -> Vec<Diagnostic> {
+    let mut diagnostics = vec![];
+
+    check_downs(game_index, game, &mut diagnostics);
+    check_quarters(game_index, game, &mut diagnostics);
+    check_kickoffs(game_index, game, &mut diagnostics);
+    check_pats(game_index, game, &mut diagnostics);
+    check_field_goals(game_index, game, &mut diagnostics);
+
+    diagnostics.sort_by_key(|diagnostic| diagnostic.event_index);
+    diagnostics
+}
+
+/// Downs must either advance by one or reset to first, except that a penalty that
+/// was not declined may have the down replayed. Plays record the down they leave the offence on,
+/// so every possession implicitly starts from first down.
+fn check_downs(game_index: usize, game: &Game, diagnostics: &mut Vec<Diagnostic>) {
+    let mut previous: Option<Down> = None;
+    let mut penalised = false;
+
+    for (idx, event) in game.events.iter().enumerate() {
+        match event {
+            Event::Kickoff(..) | Event::Punt { .. } | Event::Turnover(..) => {
+                previous = Some(Down::First);
+                penalised = false;
+            }
+            Event::Score(..) => previous = None,
+            Event::Penalty(_, details) => {
+                penalised = !details
+                    .as_ref()
+                    .is_some_and(|details| details.ruling == Ruling::Declined)
+            }
+            Event::Play(play) => {
+                let down = match &play.down {
+                    Some(down) => down.to_owned(),
+                    None => {
+                        previous = None;
+                        continue;
+                    }
+                };
+
+                if let Some(prev) = &previous {
+                    let advanced = prev.next().as_ref() == Some(&down);
+                    let replayed = penalised && *prev == down;
+
+                    if down != Down::First && !advanced && !replayed {
+                        diagnostics.push(Diagnostic::error(
+                            game_index,
+                            idx,
+                            format!("Down goes from {:?} to {:?}", prev, down),
+                        ))
+                    }
+                }
+
+                previous = Some(down);
+                penalised = false;
+            }
+            Event::Quarter(_) | Event::FieldGoalAttempt { .. } => (),
+        }
+    }
+}
+
+/// Quarter markers must strictly increase.
+fn check_quarters(game_index: usize, game: &Game, diagnostics: &mut Vec<Diagnostic>) {
+    let mut seen: Vec<Quarter> = vec![];
+
+    for (idx, event) in game.events.iter().enumerate() {
+        let quarter = match event.quarter() {
+            Some(quarter) => quarter,
+            None => continue,
+        };
+
+        if seen.contains(&quarter) {
+            diagnostics.push(Diagnostic::error(
+                game_index,
+                idx,
+                format!("Duplicate {:?} quarter marker", quarter),
+            ))
+        } else if let Some(last) = seen.last()
+            && *last > quarter
+        {
+            diagnostics.push(Diagnostic::error(
+                game_index,
+                idx,
+                format!("{:?} quarter marker follows {:?}", quarter, last),
+            ))
+        }
+
+        seen.push(quarter);
+    }
+}
+
+/// Each half must open with a kickoff.
+fn check_kickoffs(game_index: usize, game: &Game, diagnostics: &mut Vec<Diagnostic>) {
+    for (idx, event) in game.events.iter().enumerate() {
+        let quarter = match event.quarter() {
+            Some(quarter @ (Quarter::First | Quarter::Third)) => quarter,
+            _ => continue,
+        };
+
+        let opener = game.events[idx + 1..]
+            .iter()
+            .find(|event| event.quarter().is_none());
+
+        if !matches!(opener, Some(Event::Kickoff(..))) {
+            diagnostics.push(Diagnostic::warning(
+                game_index,
+                idx,
+                format!("{:?} quarter does not open with a kickoff", quarter),
+            ))
+        }
+    }
+}
+
+/// Points after touchdown must follow a touchdown.
+fn check_pats(game_index: usize, game: &Game, diagnostics: &mut Vec<Diagnostic>) {
+    let mut previous: Option<&Event> = None;
+
+    for (idx, event) in game.events.iter().enumerate() {
+        if let Event::Quarter(_) = event {
+            continue;
+        }
+
+        if let Event::Score(points, _) = event
+            && points.is_pat()
+            && !matches!(previous, Some(Event::Score(ScorePoints::Touchdown, _)))
+        {
+            diagnostics.push(Diagnostic::error(
+                game_index,
+                idx,
+                format!("{:?} does not follow a Touchdown", points),
+            ))
+        }
+
+        previous = Some(event);
+    }
+}
+
+/// A good field goal attempt must be followed by its `Score(FieldGoal)`,
+/// and one that was not good must not be.
+fn check_field_goals(game_index: usize, game: &Game, diagnostics: &mut Vec<Diagnostic>) {
+    let mut previous: Option<&FieldGoalResult> = None;
+
+    for (idx, event) in game.events.iter().enumerate() {
+        if let Event::Quarter(_) = event {
+            continue;
+        }
+
+        let scored = matches!(event, Event::Score(ScorePoints::FieldGoal, _));
+
+        match previous {
+            Some(FieldGoalResult::Good) if !scored => diagnostics.push(Diagnostic::error(
+                game_index,
+                idx,
+                "Good field goal attempt is not followed by a FieldGoal score".to_string(),
+            )),
+            Some(result @ (FieldGoalResult::Missed | FieldGoalResult::Blocked)) if scored => {
+                diagnostics.push(Diagnostic::error(
+                    game_index,
+                    idx,
+                    format!("FieldGoal scored from a {:?} attempt", result),
+                ))
+            }
+            _ => (),
+        }
+
+        previous = match event {
+            Event::FieldGoalAttempt { result, .. } => Some(result),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::play;
+    use crate::validate::*;
+    use crate::*;
+
+    fn game(events: Vec<Event>) -> Game {
+        Game {
+            version: crate::MIN_VER,
+            flags: vec![],
+            events,
+        }
+    }
+
+    #[test]
+    fn downs() {
+        let a = game(vec![
+            Event::Quarter(Quarter::First),
+            Event::Kickoff(Team::Nebraska, None, None),
+            play(Action::Unknown, Down::First, None),
+            play(Action::Unknown, Down::Second, None),
+            play(Action::Unknown, Down::Fourth, None),
+            Event::Penalty(TerrainState::Yards(5), None),
+            play(Action::Unknown, Down::Fourth, None),
+            play(Action::Unknown, Down::First, None),
+            Event::Turnover(Team::Iowa, None),
+            play(Action::Unknown, Down::Second, None),
+            Event::Kickoff(Team::Nebraska, None, None),
+            play(Action::Unknown, Down::Third, None),
+        ]);
+
+        let diagnostics = validate_game(0, &a);
+
+        assert!(diagnostics.len() == 2);
+        assert!(diagnostics[0].is_error() && diagnostics[0].event_index == Some(4));
+        assert!(diagnostics[1].is_error() && diagnostics[1].event_index == Some(11));
+
+        let declined = game(vec![
+            Event::Kickoff(Team::Nebraska, None, None),
+            play(Action::Unknown, Down::Second, None),
+            Event::Penalty(
+                TerrainState::Yards(5),
+                Some(Penalty {
+                    ruling: Ruling::Declined,
+                    ..Default::default()
+                }),
+            ),
+            play(Action::Unknown, Down::Second, None),
+        ]);
+
+        assert!(validate_game(0, &declined).len() == 1);
+    }
+
+    #[test]
+    fn quarters() {
+        let a = game(vec![
+            Event::Quarter(Quarter::First),
+            Event::Kickoff(Team::Nebraska, None, None),
+            Event::Quarter(Quarter::Third),
+            Event::Kickoff(Team::Iowa, None, None),
+            Event::Quarter(Quarter::Second),
+            Event::Quarter(Quarter::Second),
+        ]);
+
+        let diagnostics = validate_game(0, &a);
+
+        assert!(diagnostics.len() == 2);
+        assert!(diagnostics[0].event_index == Some(4));
+        assert!(diagnostics[1].message.contains("Duplicate"));
+    }
+
+    #[test]
+    fn kickoffs() {
+        let a = game(vec![
+            Event::Quarter(Quarter::First),
+            play(Action::Unknown, Down::First, None),
+            Event::Quarter(Quarter::Second),
+            play(Action::Unknown, Down::First, None),
+            Event::Quarter(Quarter::Third),
+            Event::Kickoff(Team::Iowa, None, None),
+        ]);
+
+        let diagnostics = validate_game(0, &a);
+
+        assert!(diagnostics.len() == 1);
+        assert!(diagnostics[0].severity == Severity::Warning);
+        assert!(diagnostics[0].event_index == Some(0));
+    }
+
+    #[test]
+    fn pats() {
+        let a = game(vec![
+            Event::Quarter(Quarter::First),
+            Event::Kickoff(Team::Nebraska, None, None),
+            Event::Score(ScorePoints::Touchdown, None),
+            Event::Quarter(Quarter::Second),
+            Event::Score(ScorePoints::PatFieldGoal, None),
+            Event::Kickoff(Team::Iowa, None, None),
+            Event::Score(ScorePoints::FieldGoal, None),
+            Event::Score(ScorePoints::PatFail, None),
+        ]);
+
+        let diagnostics = validate_game(0, &a);
+
+        assert!(diagnostics.len() == 1);
+        assert!(diagnostics[0].event_index == Some(7));
+    }
+
+    #[test]
+    fn field_goals() {
+        let attempt = |result: FieldGoalResult| Event::FieldGoalAttempt {
+            distance: 40,
+            result,
+        };
+
+        let a = game(vec![
+            Event::Quarter(Quarter::First),
+            Event::Kickoff(Team::Nebraska, None, None),
+            attempt(FieldGoalResult::Good),
+            Event::Score(ScorePoints::FieldGoal, None),
+            Event::Kickoff(Team::Iowa, None, None),
+            attempt(FieldGoalResult::Good),
+            Event::Kickoff(Team::Nebraska, None, None),
+            attempt(FieldGoalResult::Blocked),
+            Event::Score(ScorePoints::FieldGoal, None),
+        ]);
+
+        let diagnostics = validate_game(0, &a);
+
+        assert!(diagnostics.len() == 2);
+        assert!(diagnostics[0].event_index == Some(6));
+        assert!(diagnostics[1].event_index == Some(8));
+    }
+}
@@ -1,10 +1,19 @@
 mod tui;
 
-use clap::{ArgAction, Parser};
-use core::panic;
-use gamelog::{Action, Down, Flags, Key, LogFile, Team};
+use clap::{ArgAction, Parser, Subcommand};
+use gamelog::{
+    LogFile, Team, VersionPolicy,
+    error::MigrateError,
+    model::{
+        expected_points::ExpectedPoints,
+        markov::{DriveChain, DriveOutcome},
+    },
+    ranking::{Ranking, RatingSystem},
+    source::Source,
+    stats,
+};
 use std::{io, path::PathBuf, sync::mpsc, thread};
-use tui::App;
+use tui::{App, PATTERN_LENGTHS};
 
 #[derive(Debug, Parser)]
 #[clap(author, version, about)]
@@ -13,6 +22,7 @@ struct Args {
     #[arg(
         short,
         long,
+        global = true,
         value_hint = clap::ValueHint::DirPath,
         default_value = format!("../templates/logfile.ron")
     )]
@@ -24,80 +34,359 @@ struct Args {
     /// Provide flag to disable tui and dump info via Debug pretty printing.
     #[arg(short, long, action=ArgAction::SetTrue)]
     no_tui: bool,
+
+    /// Attempt to load games from newer minor versions of the format, with warnings.
+    #[arg(short, long, global = true, action=ArgAction::SetTrue)]
+    best_effort: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Upgrade a logfile written in an older format to the current one.
+    Migrate {
+        /// Path to the logfile to upgrade.
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        input: PathBuf,
+
+        /// Path to write the upgraded logfile to.
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        output: PathBuf,
+    },
+    /// Check the logfile for transcription mistakes, failing on any errors.
+    Check,
+    /// Test whether two teams differ in their deltas and play calling.
+    Compare {
+        /// e.g. Nebraska
+        a: Team,
+        /// e.g. Iowa
+        b: Team,
+    },
+    /// Rank every team by Elo rating, rated game by game.
+    Rank {
+        /// Scale each rating change by the margin of victory.
+        #[arg(short, long, action=ArgAction::SetTrue)]
+        margin: bool,
+    },
 }
 
 fn main() -> io::Result<()> {
     let config = Args::parse();
 
-    let log: LogFile = match LogFile::try_from(config.logfile_path) {
-        Ok(f) => f,
-        Err(err) => panic!("Error: Failed to open logfile: {:?}", err),
+    if let Some(Command::Migrate { input, output }) = config.command {
+        let source = read_source(input);
+
+        let log = match gamelog::migrate::migrate(&source.text) {
+            Ok(log) => log,
+            Err(MigrateError::RonSpannedError(err)) => fail(source.render_spanned(&err)),
+            Err(err) => fail(format!("error: Failed to migrate logfile: {}", err)),
+        };
+
+        if let Err(err) = log.write_to(output) {
+            fail(format!("error: Failed to write logfile: {}", err))
+        }
+
+        return Ok(());
+    }
+
+    let policy = if config.best_effort {
+        VersionPolicy::BestEffort
+    } else {
+        VersionPolicy::Strict
     };
 
+    let source = read_source(config.logfile_path);
+
+    let log: LogFile = match LogFile::parse(&source.text, policy) {
+        Ok((f, warnings)) => {
+            warnings
+                .iter()
+                .for_each(|warning| eprintln!("warning: {}", warning));
+
+            f
+        }
+        Err(err) => fail(source.render_error(&err)),
+    };
+
+    if let Some(Command::Check) = config.command {
+        let diagnostics = gamelog::validate::validate(&log);
+
+        diagnostics
+            .iter()
+            .for_each(|diagnostic| eprintln!("{}\n", source.render_diagnostic(diagnostic)));
+
+        if diagnostics.iter().any(|diagnostic| diagnostic.is_error()) {
+            std::process::exit(1)
+        }
+
+        return Ok(());
+    }
+
+    if let Some(Command::Compare { a, b }) = config.command {
+        let comparison = stats::Comparison::new(&log, a, b);
+        let (a, b) = (&comparison.a, &comparison.b);
+
+        println!("{:?} vs {:?}", a.team, b.team);
+
+        for (metric, test) in [
+            ("delta", &comparison.delta),
+            ("gain", &comparison.gain),
+            ("loss", &comparison.loss),
+        ] {
+            match test {
+                Some(test) => println!("{}: {}", metric, test),
+                None => println!("{}: not enough samples", metric),
+            }
+        }
+
+        for (team, summary) in [(a, a.delta_summary()), (b, b.delta_summary())] {
+            match summary.and_then(|summary| summary.confidence_interval(0.95)) {
+                Some(interval) => println!("{:?} mean delta: {}", team.team, interval),
+                None => println!("{:?} mean delta: not enough samples", team.team),
+            }
+        }
+
+        for (label, test) in [
+            ("play calls".to_string(), &comparison.play_calls),
+            (
+                format!("{:?} play calls vs league", a.team),
+                &comparison.a_against_league,
+            ),
+            (
+                format!("{:?} play calls vs league", b.team),
+                &comparison.b_against_league,
+            ),
+        ] {
+            match test {
+                Some(test) => println!("{}: {}", label, test),
+                None => println!("{}: not enough samples", label),
+            }
+        }
+
+        return Ok(());
+    }
+
+    if let Some(Command::Rank { margin }) = config.command {
+        let system = if margin {
+            RatingSystem::MarginOfVictory
+        } else {
+            RatingSystem::Elo
+        };
+
+        println!("{}", Ranking::new(&log, system));
+
+        return Ok(());
+    }
+
     if config.no_tui {
-        let mut stats = vec![
-            TeamStats::new(Team::ArizonaState),
-            #[allow(deprecated)]
-            TeamStats::new(Team::BoiseState),
-            TeamStats::new(Team::Colorado),
-            TeamStats::new(Team::Iowa),
-            TeamStats::new(Team::Nebraska),
-            TeamStats::new(Team::Syracuse),
-            TeamStats::new(Team::SouthCarolina),
-            TeamStats::new(Team::TexasAnM),
-        ];
-
-        // Work on knocking down the nesting here?
-        for game in log.0.iter() {
-            let teams = match game.teams() {
-                Ok(teams) => teams,
-                Err(_) => continue,
-            };
-
-            for team in teams {
-                // Skip team if they are to be ignored this game.
-                if game.flags.contains(&Flags::IgnoreTeam(team.to_owned())) {
-                    continue;
+        let stats = stats::team_stats(&log);
+
+        // :#? for pretty-printing.
+        stats.iter().for_each(|team| println!("{:#?}", team));
+
+        for team in &stats {
+            for (metric, summary) in [
+                ("gain", team.gain_summary()),
+                ("loss", team.loss_summary()),
+                ("delta", team.delta_summary()),
+                ("plays per quarter", team.plays_per_quarter_summary()),
+            ] {
+                match summary {
+                    Some(summary) => println!("{:?} {}: {}", team.team, metric, summary),
+                    None => println!("{:?} {}: no samples", team.team, metric),
                 }
+            }
+        }
+
+        let model = ExpectedPoints::fit(&log);
+
+        println!("Expected points over {} plays:", model.samples());
+
+        model
+            .values()
+            .iter()
+            .for_each(|value| println!("  {}", value));
+
+        for team in &stats {
+            println!("{:?} play calls:", team.team);
+
+            let calls = stats::play_calls(&log, team.team.to_owned());
+
+            calls
+                .iter()
+                .filter(|call| call.count > 0)
+                .for_each(|call| println!("  {}", call));
+
+            if let Some(best) = stats::most_effective_play(&calls) {
+                println!("{:?} most effective play: {:?}", team.team, best.choice);
+            }
+
+            println!("{:?} play calls by EPA:", team.team);
+
+            model
+                .call_values(&log, team.team.to_owned())
+                .iter()
+                .for_each(|call| println!("  {}", call));
 
-                let team_idx = stats
+            println!("{:?} playsets:", team.team);
+
+            stats::playset_calls(&log, team.team.to_owned())
+                .iter()
+                .filter(|call| call.count > 0)
+                .for_each(|call| println!("  {}", call));
+
+            println!("{:?} keys:", team.team);
+
+            stats::key_calls(&log, team.team.to_owned())
+                .iter()
+                .filter(|call| call.count > 0)
+                .for_each(|call| println!("  {}", call));
+
+            for length in PATTERN_LENGTHS {
+                println!("{:?} {}-play patterns:", team.team, length);
+
+                stats::play_patterns(&log, team.team.to_owned(), length)
                     .iter()
-                    .position(|stat| stat.team == team.to_owned())
-                    .unwrap();
+                    .take(5)
+                    .for_each(|pattern| println!("  {}", pattern));
+            }
+
+            println!("{:?} situations:", team.team);
+
+            stats::situational_splits(&log, team.team.to_owned())
+                .iter()
+                .filter(|split| split.plays > 0)
+                .for_each(|split| println!("  {}", split));
+
+            match team.downs_to_convert_summary() {
+                Some(summary) => println!("{:?} downs to convert: {}", team.team, summary),
+                None => println!("{:?} downs to convert: no samples", team.team),
+            }
+
+            println!("{:?} biases:", team.team);
 
-                stats[team_idx]
-                    .avg_terrain_gain
-                    .push(game.avg_gain(team.to_owned()));
+            for rate in stats::bias_rates(&log, team.team.to_owned())
+                .iter()
+                .filter(|rate| rate.plays > 0)
+            {
+                match &rate.against_league {
+                    Some(test) => println!("  {}, vs league p {:.4}", rate, test.p_value),
+                    None => println!("  {}", rate),
+                }
+            }
+
+            println!(
+                "{:?} efficiency: {}",
+                team.team,
+                stats::efficiency(&log, team.team.to_owned())
+            );
+
+            stats::efficiency_by_game(&log, team.team.to_owned())
+                .iter()
+                .for_each(|(idx, efficiency)| println!("  game {}: {}", idx, efficiency));
+
+            println!("{:?} efficiency by action:", team.team);
+
+            stats::efficiency_by_action(&log, team.team.to_owned())
+                .iter()
+                .filter(|(_, efficiency)| efficiency.runs + efficiency.passes > 0)
+                .for_each(|(action, efficiency)| println!("  {:?}: {}", action, efficiency));
+        }
+
+        for team in &stats {
+            let chain = DriveChain::new(&log, Some(team.team.to_owned()));
 
-                stats[team_idx]
-                    .avg_terrain_loss
-                    .push(game.avg_loss(team.to_owned()));
+            println!("{:?} drive transitions:\n{}", team.team, chain);
 
-                stats[team_idx]
-                    .avg_terrain_delta
-                    .push(game.avg_delta(team.to_owned()));
+            if let Some(probabilities) = chain.absorption_probabilities() {
+                println!("{:?} drive outcomes:", team.team);
 
-                stats[team_idx]
-                    .plays_per_quarter
-                    .push(game.avg_plays_per_quarter(team.to_owned()));
+                for ((state, row), total) in
+                    chain.states.iter().zip(probabilities).zip(chain.totals())
+                {
+                    if total == 0 {
+                        continue;
+                    }
 
-                stats[team_idx]
-                    .plays_per_game
-                    .push(game.team_plays(team.to_owned()));
+                    let outcomes: Vec<String> = DriveOutcome::all()
+                        .iter()
+                        .zip(row)
+                        .map(|(outcome, p)| format!("{:?} {:.3}", outcome, p))
+                        .collect();
 
-                stats[team_idx]
-                    .penalties_per_game
-                    .push(game.penalties(team.to_owned()));
+                    println!("  {}: {}", state, outcomes.join(", "));
+                }
             }
         }
 
-        // :#? for pretty-printing.
-        stats.iter().for_each(|team| println!("{:#?}", team));
+        for team in &stats {
+            let momentum = stats::Momentum::new(&log, team.team.to_owned());
+
+            for bucket in &momentum.buckets {
+                println!(
+                    "{:?} {:?}: {} drives, plays per drive {}, delta {}, run share {}",
+                    team.team,
+                    bucket.situation,
+                    bucket.plays_per_drive.len(),
+                    bucket
+                        .plays_summary()
+                        .map_or("n/a".to_string(), |summary| format!("{:.2}", summary.mean)),
+                    bucket
+                        .delta_summary()
+                        .map_or("n/a".to_string(), |summary| format!("{:.2}", summary.mean)),
+                    bucket
+                        .run_share()
+                        .map_or("n/a".to_string(), |share| format!("{:.1}%", share * 100.0)),
+                );
+            }
+
+            for (label, test) in [
+                ("plays per drive after scoring", &momentum.own_score_plays),
+                ("delta after scoring", &momentum.own_score_delta),
+                (
+                    "plays per drive after conceding",
+                    &momentum.opponent_score_plays,
+                ),
+                ("delta after conceding", &momentum.opponent_score_delta),
+            ] {
+                match test {
+                    Some(test) => println!("{:?} {} vs baseline: {}", team.team, label, test),
+                    None => println!("{:?} {} vs baseline: not enough samples", team.team, label),
+                }
+            }
+
+            match &momentum.run_pass_mix {
+                Some(test) => println!("{:?} run/pass mix by situation: {}", team.team, test),
+                None => println!(
+                    "{:?} run/pass mix by situation: not enough samples",
+                    team.team
+                ),
+            }
+
+            if let Some(slows) = momentum.slows_after_score(0.05) {
+                println!("{:?} slows after scoring: {}", team.team, slows);
+            }
+        }
+
+        log.0
+            .iter()
+            .enumerate()
+            .for_each(|(idx, game)| match game.scoreboard() {
+                Some(scoreboard) => println!(
+                    "Game {}: {:?}, winner: {:?}",
+                    idx,
+                    scoreboard.final_score(),
+                    scoreboard.winner()
+                ),
+                None => println!("Game {}: scores ignored", idx),
+            });
 
         return Ok(());
     }
 
-    let mut app = App { exit: false };
+    let mut app = App::new(log);
 
     // Enter Raw terminal mode.
     let mut terminal = ratatui::init();
@@ -115,48 +404,15 @@ fn main() -> io::Result<()> {
     app_result
 }
 
-#[derive(Debug)]
-struct TeamStats {
-    team: gamelog::Team,
-    // Terrain
-    avg_terrain_gain: Vec<f32>,
-    avg_terrain_loss: Vec<f32>,
-    avg_terrain_delta: Vec<f32>,
-    // Play rate
-    plays_per_quarter: Vec<f32>,
-    plays_per_game: Vec<usize>,
-    // Penalties
-    penalties_per_game: Vec<usize>,
-    // Score
-    points_per_quarter: Vec<u8>,
-    points_per_game: Vec<u8>,
-    // Biases
-    most_common_play: Option<Action>,
-    least_common_play: Option<Action>,
-    most_common_key: Option<Key>,
-    least_common_key: Option<Key>,
-    // Traits
-    // Typical number of downs to achieve 10 yards.
-    time_to_first_down: Option<Down>,
+/// Prints `message` and exits unsuccessfully.
+fn fail(message: String) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1)
 }
 
-impl TeamStats {
-    fn new(team: Team) -> Self {
-        TeamStats {
-            team,
-            avg_terrain_gain: vec![],
-            avg_terrain_loss: vec![],
-            avg_terrain_delta: vec![],
-            plays_per_quarter: vec![],
-            plays_per_game: vec![],
-            penalties_per_game: vec![],
-            points_per_quarter: vec![],
-            points_per_game: vec![],
-            most_common_play: None,
-            least_common_play: None,
-            most_common_key: None,
-            least_common_key: None,
-            time_to_first_down: None,
-        }
+fn read_source(path: PathBuf) -> Source {
+    match Source::read(path) {
+        Ok(source) => source,
+        Err(err) => fail(format!("error: Failed to open logfile: {}", err)),
     }
 }
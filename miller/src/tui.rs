@@ -1,5 +1,9 @@
-use std::{io, sync::mpsc};
+use std::{fmt, io, sync::mpsc};
 
+use gamelog::{
+    Down, LogFile,
+    stats::{self, TeamStats, Tendency, conversions_on},
+};
 use ratatui::{
     DefaultTerminal, Frame,
     crossterm::{
@@ -7,21 +11,38 @@ use ratatui::{
         event::{KeyCode, KeyEventKind},
     },
     layout::{Constraint, Layout},
-    style::Color as Colour,
+    style::{Modifier, Style},
     symbols::border,
-    text::Line,
-    widgets::{Block, Widget},
+    text::{Line, Span, Text},
+    widgets::{Block, Row, Table, Widget},
 };
 
+/// Lengths of the play sequences searched for repeats.
+pub const PATTERN_LENGTHS: std::ops::RangeInclusive<usize> = 2..=4;
+
 pub enum Event {
     Input(crossterm::event::KeyEvent),
 }
 
 pub struct App {
     pub exit: bool,
+    pub log: LogFile,
+    /// Every team in the log, in the order they first appear.
+    pub teams: Vec<TeamStats>,
+    /// Index into `teams` of the team being shown.
+    pub selected: usize,
 }
 
 impl App {
+    pub fn new(log: LogFile) -> Self {
+        Self {
+            exit: false,
+            teams: stats::team_stats(&log),
+            log,
+            selected: 0,
+        }
+    }
+
     pub fn run(
         &mut self,
         terminal: &mut DefaultTerminal,
@@ -46,8 +67,21 @@ impl App {
     }
 
     pub fn handle_key_event(&mut self, key_event: crossterm::event::KeyEvent) -> io::Result<()> {
-        if key_event.kind == KeyEventKind::Press && key_event.code == KeyCode::Char('q') {
-            self.exit = true;
+        if key_event.kind != KeyEventKind::Press || self.teams.is_empty() {
+            if key_event.code == KeyCode::Char('q') {
+                self.exit = true;
+            }
+
+            return Ok(());
+        }
+
+        match key_event.code {
+            KeyCode::Char('q') => self.exit = true,
+            KeyCode::Left => {
+                self.selected = (self.selected + self.teams.len() - 1) % self.teams.len()
+            }
+            KeyCode::Right => self.selected = (self.selected + 1) % self.teams.len(),
+            _ => (),
         }
 
         Ok(())
@@ -100,12 +134,34 @@ impl Widget for &App {
         let teams_block = Block::bordered()
             .title(Line::from(" Teams "))
             .border_set(border::THICK);
+
+        let teams = Line::from(
+            self.teams
+                .iter()
+                .enumerate()
+                .flat_map(|(idx, stats)| {
+                    let name = Span::from(format!(" {:?} ", stats.team));
+
+                    if idx == self.selected {
+                        [
+                            name.style(Style::new().add_modifier(Modifier::REVERSED)),
+                            " ".into(),
+                        ]
+                    } else {
+                        [name, " ".into()]
+                    }
+                })
+                .collect::<Vec<Span>>(),
+        );
+        teams.render(teams_block.inner(teams_area), buf);
         teams_block.render(teams_area, buf);
 
         let instructions = Line::from(vec![
             " ".into(),
             "Quit <q>".into(),
             " | ".into(),
+            "Team <←/→>".into(),
+            " | ".into(),
             "Function <a>".into(),
             " | ".into(),
             "Function <b>".into(),
@@ -130,21 +186,151 @@ impl Widget for &App {
         let pattern_block = Block::bordered()
             .title(" Pattern ")
             .border_set(border::THICK);
+
+        if let Some(team) = self.teams.get(self.selected).map(|stats| &stats.team) {
+            let mut lines: Vec<Line> = vec![];
+
+            for length in PATTERN_LENGTHS {
+                lines.push(
+                    Line::from(format!("{} plays", length))
+                        .style(Style::new().add_modifier(Modifier::BOLD)),
+                );
+
+                lines.extend(
+                    stats::play_patterns(&self.log, team.to_owned(), length)
+                        .iter()
+                        .take(3)
+                        .map(|pattern| Line::from(format!(" {}", pattern))),
+                );
+            }
+
+            Text::from(lines).render(pattern_block.inner(pattern_area), buf);
+        }
+
         pattern_block.render(pattern_area, buf);
 
         let common_play_block = Block::bordered()
             .title(" Most Freq. Play ")
             .border_set(border::THICK);
+
+        if let Some(team) = self.teams.get(self.selected).map(|stats| &stats.team) {
+            let rows = tendency_rows(&stats::play_calls(&self.log, team.to_owned()));
+
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(3),
+                    Constraint::Fill(1),
+                    Constraint::Length(5),
+                    Constraint::Length(6),
+                    Constraint::Length(6),
+                    Constraint::Length(7),
+                ],
+            )
+            .header(
+                Row::new(vec!["#", "Play", "Calls", "Share", "Gain", "Success"])
+                    .style(Style::new().add_modifier(Modifier::BOLD)),
+            );
+
+            Widget::render(table, common_play_block.inner(common_play_area), buf);
+        }
+
         common_play_block.render(common_play_area, buf);
 
         let common_config_block = Block::bordered()
             .title(" Most Freq. Configuration ")
             .border_set(border::THICK);
+
+        if let Some(team) = self.teams.get(self.selected).map(|stats| &stats.team) {
+            let [playset_area, key_area] =
+                Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .areas(common_config_block.inner(common_config_area));
+
+            for (title, rows, area) in [
+                (
+                    "Playset",
+                    tendency_rows(&stats::playset_calls(&self.log, team.to_owned())),
+                    playset_area,
+                ),
+                (
+                    "Key",
+                    tendency_rows(&stats::key_calls(&self.log, team.to_owned())),
+                    key_area,
+                ),
+            ] {
+                let table = Table::new(
+                    rows,
+                    [
+                        Constraint::Length(3),
+                        Constraint::Fill(1),
+                        Constraint::Length(5),
+                        Constraint::Length(6),
+                        Constraint::Length(6),
+                        Constraint::Length(7),
+                    ],
+                )
+                .header(
+                    Row::new(vec!["#", title, "Calls", "Share", "Gain", "Success"])
+                        .style(Style::new().add_modifier(Modifier::BOLD)),
+                );
+
+                Widget::render(table, area, buf);
+            }
+        }
+
         common_config_block.render(common_config_area, buf);
 
         let trends_block = Block::bordered()
             .title(" Trends ")
             .border_set(border::THICK);
+
+        if let Some(stats) = self.teams.get(self.selected) {
+            let mut lines: Vec<Line> = vec![];
+
+            for down in [Down::Third, Down::Fourth] {
+                let (converted, attempts) = conversions_on(&stats.conversions, &down);
+                let mut spans: Vec<Span> = vec![
+                    Span::from(format!("{:?} down {}/{}", down, converted, attempts))
+                        .style(Style::new().add_modifier(Modifier::BOLD)),
+                ];
+
+                for count in stats
+                    .conversions
+                    .iter()
+                    .filter(|count| count.down == down && count.attempts > 0)
+                {
+                    spans.push(
+                        match &count.distance {
+                            Some(distance) => format!(
+                                " | {:?} {}/{}",
+                                distance, count.conversions, count.attempts
+                            ),
+                            None => format!(" | ? {}/{}", count.conversions, count.attempts),
+                        }
+                        .into(),
+                    );
+                }
+
+                lines.push(Line::from(spans));
+            }
+
+            for (label, rates) in [
+                ("3rd by game", &stats.third_down_rate_per_game),
+                ("4th by game", &stats.fourth_down_rate_per_game),
+            ] {
+                let rates: Vec<String> = rates
+                    .iter()
+                    .map(|rate| {
+                        rate.map_or("-".to_string(), |rate| format!("{:.0}%", rate * 100.0))
+                    })
+                    .collect();
+
+                lines.push(Line::from(format!("{}: {}", label, rates.join(" "))));
+            }
+
+            Text::from(lines).render(trends_block.inner(trends_area), buf);
+        }
+
         trends_block.render(trends_area, buf);
 
         let graph_block = Block::bordered().title(" Graph ").border_set(border::THICK);
@@ -162,12 +348,31 @@ impl Widget for &App {
     }
 }
 
+/// One row per choice that was picked at least once.
+fn tendency_rows<T: fmt::Debug>(calls: &[Tendency<T>]) -> Vec<Row<'static>> {
+    calls
+        .iter()
+        .filter(|call| call.count > 0)
+        .map(|call| {
+            Row::new(vec![
+                format!("{}", call.rank),
+                format!("{:?}", call.choice),
+                format!("{}", call.count),
+                format!("{:.1}%", call.share * 100.0),
+                call.mean_gain
+                    .map_or("-".to_string(), |gain| format!("{:.2}", gain)),
+                call.success_rate
+                    .map_or("-".to_string(), |rate| format!("{:.1}%", rate * 100.0)),
+            ])
+        })
+        .collect()
+}
+
 pub fn input_fetcher(tx: mpsc::Sender<Event>) {
     loop {
         // unwraps, bc what could go wrong?
-        match crossterm::event::read().unwrap() {
-            crossterm::event::Event::Key(key_event) => tx.send(Event::Input(key_event)).unwrap(),
-            _ => (),
+        if let crossterm::event::Event::Key(key_event) = crossterm::event::read().unwrap() {
+            tx.send(Event::Input(key_event)).unwrap()
         }
     }
 }